@@ -1,6 +1,7 @@
 use node_template_runtime::{
-	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, Signature, SudoConfig,
-	SystemConfig, WASM_BINARY,
+	pallet_kitties::{KittyDna, KittyId, KittyName, DEFAULT_COLLECTION},
+	AccountId, AuraConfig, BalancesConfig, GenesisConfig, GrandpaConfig, KittiesModuleConfig,
+	Signature, SudoConfig, SystemConfig, WASM_BINARY,
 };
 use sc_service::ChainType;
 use sp_consensus_aura::sr25519::AuthorityId as AuraId;
@@ -59,6 +60,13 @@ pub fn development_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Alice//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Bob//stash"),
 				],
+				// Initial kitties
+				vec![
+					(get_account_id_from_seed::<sr25519::Public>("Alice"), *b"Garfield", None),
+					(get_account_id_from_seed::<sr25519::Public>("Bob"), *b"Tom-Cat!", None),
+				],
+				// Kitties on sale
+				vec![(DEFAULT_COLLECTION, 1)],
 				true,
 			)
 		},
@@ -107,6 +115,14 @@ pub fn local_testnet_config() -> Result<ChainSpec, String> {
 					get_account_id_from_seed::<sr25519::Public>("Eve//stash"),
 					get_account_id_from_seed::<sr25519::Public>("Ferdie//stash"),
 				],
+				// Initial kitties
+				vec![
+					(get_account_id_from_seed::<sr25519::Public>("Alice"), *b"Garfield", None),
+					(get_account_id_from_seed::<sr25519::Public>("Bob"), *b"Tom-Cat!", None),
+					(get_account_id_from_seed::<sr25519::Public>("Charlie"), *b"Felix---", None),
+				],
+				// Kitties on sale
+				vec![(DEFAULT_COLLECTION, 1), (DEFAULT_COLLECTION, 2)],
 				true,
 			)
 		},
@@ -130,6 +146,8 @@ fn testnet_genesis(
 	initial_authorities: Vec<(AuraId, GrandpaId)>,
 	root_key: AccountId,
 	endowed_accounts: Vec<AccountId>,
+	initial_kitties: Vec<(AccountId, KittyName, Option<KittyDna>)>,
+	kitties_on_sale: Vec<KittyId>,
 	_enable_println: bool,
 ) -> GenesisConfig {
 	GenesisConfig {
@@ -152,5 +170,10 @@ fn testnet_genesis(
 			key: Some(root_key),
 		},
 		transaction_payment: Default::default(),
+		kitties_module: KittiesModuleConfig {
+			kitties: initial_kitties,
+			next_item_id: 0,
+			on_sale: kitties_on_sale,
+		},
 	}
 }
@@ -0,0 +1,122 @@
+//! RPC interface for the kitties pallet.
+
+use std::{convert::TryInto, sync::Arc};
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::traits::{Block as BlockT, MaybeDisplay};
+
+pub use pallet_kitties_runtime_api::{CollectionId, KittiesApi as KittiesRuntimeApi, KittyId};
+
+#[rpc(client, server)]
+pub trait KittiesApi<BlockHash, Balance> {
+	/// Price of the next `create` or `breed` in `collection_id`.
+	#[method(name = "kitties_price")]
+	fn price(&self, collection_id: CollectionId, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	/// Funds collected in the pallet account.
+	#[method(name = "kitties_potBalance")]
+	fn pot_balance(&self, at: Option<BlockHash>) -> RpcResult<NumberOrHex>;
+
+	/// Metadata URI of `kitty_id`, like the `tokenURI` of ERC-721.
+	#[method(name = "kitties_tokenUri")]
+	fn token_uri(&self, kitty_id: KittyId, at: Option<BlockHash>) -> RpcResult<Option<String>>;
+}
+
+/// Provides RPC methods to query the kitties pallet.
+pub struct Kitties<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Kitties<C, P> {
+	/// Create new `Kitties` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The balance does not fit in the response.
+	BalanceOverflow,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+			Error::BalanceOverflow => 2,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+		.into()
+}
+
+fn into_number<Balance: MaybeDisplay + Copy + TryInto<NumberOrHex>>(
+	balance: Balance,
+) -> RpcResult<NumberOrHex> {
+	balance.try_into().map_err(|_| {
+		CallError::Custom(ErrorObject::owned(
+			Error::BalanceOverflow.into(),
+			"Balance does not fit into a NumberOrHex.",
+			Some(format!("{}", balance)),
+		))
+		.into()
+	})
+}
+
+#[async_trait]
+impl<C, Block, Balance> KittiesApiServer<<Block as BlockT>::Hash, Balance> for Kitties<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: KittiesRuntimeApi<Block, Balance>,
+	Balance: Codec + MaybeDisplay + Copy + TryInto<NumberOrHex> + Send + Sync + 'static,
+{
+	fn price(
+		&self,
+		collection_id: CollectionId,
+		at: Option<Block::Hash>,
+	) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let price = api
+			.kitty_price(at_hash, collection_id)
+			.map_err(|e| runtime_error("Unable to query kitty price.", e))?;
+		into_number(price)
+	}
+
+	fn pot_balance(&self, at: Option<Block::Hash>) -> RpcResult<NumberOrHex> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let balance = api
+			.pot_balance(at_hash)
+			.map_err(|e| runtime_error("Unable to query pot balance.", e))?;
+		into_number(balance)
+	}
+
+	fn token_uri(&self, kitty_id: KittyId, at: Option<Block::Hash>) -> RpcResult<Option<String>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let uri = api
+			.token_uri(at_hash, kitty_id)
+			.map_err(|e| runtime_error("Unable to query token URI.", e))?;
+		Ok(uri.map(|uri| String::from_utf8_lossy(&uri).into_owned()))
+	}
+}
@@ -0,0 +1,37 @@
+//! Runtime API definition for the kitties pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_kitties::{AttributeNamespace, CollectionId, KittyId, Pedigree};
+
+sp_api::decl_runtime_apis! {
+	pub trait KittiesApi<Balance>
+	where
+		Balance: Codec,
+	{
+		/// Price of the next `create` or `breed` in `collection_id`.
+		fn kitty_price(collection_id: CollectionId) -> Balance;
+
+		/// Funds collected in the pallet account.
+		fn pot_balance() -> Balance;
+
+		/// Ancestor tree of `kitty_id` up to `generations` generations back, at most
+		/// `MaxPedigreeDepth`.
+		fn lineage(kitty_id: KittyId, generations: u32) -> Option<Pedigree>;
+
+		/// Kitties bred from `kitty_id`.
+		fn children(kitty_id: KittyId) -> Vec<KittyId>;
+
+		/// Whether `kitty_id` can change owner, i.e. it is not soulbound.
+		fn is_transferable(kitty_id: KittyId) -> bool;
+
+		/// Base URI of the collection of `kitty_id` followed by its item id.
+		fn token_uri(kitty_id: KittyId) -> Option<Vec<u8>>;
+
+		/// Attributes of `kitty_id`, as `(namespace, key, value)`.
+		fn attributes(kitty_id: KittyId) -> Vec<(AttributeNamespace, Vec<u8>, Vec<u8>)>;
+	}
+}
@@ -3,6 +3,9 @@
 pub use pallet::*;
 
 mod migrations;
+mod types;
+
+pub use types::*;
 
 #[cfg(test)]
 mod mock;
@@ -15,35 +18,209 @@ pub mod pallet {
 	pub use crate::migrations::current_version::*;
 
 	use crate::migrations::upgrade_storage;
+	use crate::types::*;
 	use frame_support::{
 		pallet_prelude::*,
-		traits::{Currency, ExistenceRequirement, Randomness},
+		traits::{
+			BalanceStatus, Currency, ExistenceRequirement, Randomness, ReservableCurrency,
+			WithdrawReasons,
+		},
 		PalletId,
 	};
 	use frame_system::pallet_prelude::*;
 	use sp_io::hashing::blake2_128;
-	use sp_runtime::traits::AccountIdConversion;
+	use sp_std::{collections::btree_set::BTreeSet, vec::Vec};
+	use sp_runtime::{
+		traits::{AccountIdConversion, Bounded, Saturating, UniqueSaturatedInto, Zero},
+		Permill,
+	};
 
-	type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type BalanceOf<T> = <<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	pub type AttributeKeyOf<T> = BoundedVec<u8, <T as Config>::KeyLimit>;
+	pub type AttributeValueOf<T> = BoundedVec<u8, <T as Config>::ValueLimit>;
+	pub type BaseUriOf<T> = BoundedVec<u8, <T as Config>::UriLimit>;
 
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
 		type Randomness: Randomness<Self::Hash, Self::BlockNumber>;
-		type Currency: Currency<Self::AccountId>;
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// Starting mint price of `DEFAULT_COLLECTION` until root configures it.
 		#[pallet::constant]
 		type KittyPrice: Get<BalanceOf<Self>>;
 		type PalletId: Get<PalletId>;
+		/// How much the mint price goes up after every `create` or `breed`.
+		#[pallet::constant]
+		type PriceIncrease: Get<Permill>;
+		/// How much of the mint price is lost every block without a mint.
+		#[pallet::constant]
+		type PriceDecay: Get<Permill>;
+		/// Share of the way the mint price moves towards the price of every completed `buy` or
+		/// `accept_offer`, making it an exponential moving average of sale prices.
+		#[pallet::constant]
+		type SalePriceWeight: Get<Permill>;
+		/// Origin allowed to spend or burn the funds of the pallet account.
+		type SpendOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Number of blocks between two distributions of the pot to breeders.
+		#[pallet::constant]
+		type DistributionPeriod: Get<Self::BlockNumber>;
+		/// Share of the pot paid out to the breeders of the period at each distribution.
+		#[pallet::constant]
+		type BreederShare: Get<Permill>;
+		/// Maximum number of breeds rewarded in one period. Later breeds are not rewarded.
+		#[pallet::constant]
+		type MaxRewardedBreeders: Get<u32>;
+		/// Share of the `buy` price paid to the account that created or bred the kitty.
+		#[pallet::constant]
+		type CreatorRoyalty: Get<Permill>;
+		/// Share of the `buy` price paid into the pallet account.
+		#[pallet::constant]
+		type PotRoyalty: Get<Permill>;
+		/// Maximum number of standing offers on a single kitty.
+		#[pallet::constant]
+		type MaxOffersPerKitty: Get<u32>;
+		/// Smallest amount an offer can be for, so that dust offers cannot take the slots of
+		/// `MaxOffersPerKitty`. Offers below the existential deposit are rejected in any case.
+		#[pallet::constant]
+		type MinOffer: Get<BalanceOf<Self>>;
+		/// Maximum number of blocks an offer can stand for.
+		#[pallet::constant]
+		type MaxOfferDuration: Get<Self::BlockNumber>;
+		/// Maximum number of children indexed for a single kitty. Parents with that many
+		/// children cannot breed again.
+		#[pallet::constant]
+		type MaxChildren: Get<u32>;
+		/// `breed` rejects two kitties that share an ancestor within this many generations,
+		/// including one being an ancestor of the other. Zero disables the check.
+		#[pallet::constant]
+		type InbreedingGenerations: Get<u32>;
+		/// Maximum number of generations `lineage` goes back. The tree doubles with every
+		/// generation, so keep this small.
+		#[pallet::constant]
+		type MaxPedigreeDepth: Get<u32>;
+		/// Maximum number of leases ending in the same block.
+		#[pallet::constant]
+		type MaxLeasesPerBlock: Get<u32>;
+		/// Fee paid into the prize pool to enter a kitty into a competition.
+		#[pallet::constant]
+		type EntryFee: Get<BalanceOf<Self>>;
+		/// Number of blocks between two competitions.
+		#[pallet::constant]
+		type CompetitionPeriod: Get<Self::BlockNumber>;
+		/// Maximum number of kitties in one competition.
+		#[pallet::constant]
+		type MaxEntrants: Get<u32>;
+		/// Maximum number of kitties across all collections.
+		#[pallet::constant]
+		type MaxSupply: Get<u32>;
+		/// Maximum number of kitties an account can `create` in one `MintPeriod`.
+		#[pallet::constant]
+		type MintLimitPerPeriod: Get<u32>;
+		/// Length in blocks of the periods `MintLimitPerPeriod` applies to.
+		#[pallet::constant]
+		type MintPeriod: Get<Self::BlockNumber>;
+		/// Origin allowed to manage the mint allowlist.
+		type MintOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Origin allowed to create soulbound kitties.
+		type SoulboundOrigin: EnsureOrigin<Self::RuntimeOrigin>;
+		/// Maximum length of an attribute key.
+		#[pallet::constant]
+		type KeyLimit: Get<u32>;
+		/// Maximum length of an attribute value.
+		#[pallet::constant]
+		type ValueLimit: Get<u32>;
+		/// Maximum number of attributes of a kitty, across namespaces.
+		#[pallet::constant]
+		type MaxAttributes: Get<u32>;
+		/// Maximum length of the base URI of a collection.
+		#[pallet::constant]
+		type UriLimit: Get<u32>;
+		/// Maximum number of items in one `transfer_batch`, `sale_batch` or `buy_batch`.
+		#[pallet::constant]
+		type MaxBatchSize: Get<u32>;
+		/// Maximum number of kitties on each side of a swap.
+		#[pallet::constant]
+		type MaxSwapKitties: Get<u32>;
+		/// Handler notified when kitties are created, bred, transferred or sold.
+		type OnKittyEvent: OnKittyEvent<Self::AccountId, BalanceOf<Self>>;
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_on_sale)]
 	pub type KittyOnSale<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, ()>;
 
+	/// Item id of the next kitty created or bred into each collection, which is also the number
+	/// of kitties in it.
 	#[pallet::storage]
-	#[pallet::getter(fn next_kitty_id)]
-	pub type NextKittyId<T> = StorageValue<_, KittyId, ValueQuery>;
+	#[pallet::getter(fn next_item_id)]
+	pub type NextItemId<T> = StorageMap<_, Blake2_128Concat, CollectionId, ItemId, ValueQuery>;
+
+	/// Number of kitties across all collections, capped at `MaxSupply`.
+	#[pallet::storage]
+	#[pallet::getter(fn total_supply)]
+	pub type TotalSupply<T> = StorageValue<_, u32, ValueQuery>;
+
+	/// Period of the last `create` of each account and the number of kitties it created in it.
+	#[pallet::storage]
+	#[pallet::getter(fn account_mints)]
+	pub type AccountMints<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, (T::BlockNumber, u32), ValueQuery>;
+
+	/// Whether only allowlisted accounts may `create`, e.g. during a presale.
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist_only)]
+	pub type AllowlistOnly<T> = StorageValue<_, bool, ValueQuery>;
+
+	/// Accounts allowed to `create` while `AllowlistOnly` is set.
+	#[pallet::storage]
+	#[pallet::getter(fn mint_allowlist)]
+	pub type MintAllowlist<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Soulbound kitties, which never change owner.
+	#[pallet::storage]
+	#[pallet::getter(fn non_transferable)]
+	pub type NonTransferable<T> = StorageMap<_, Blake2_128Concat, KittyId, ()>;
+
+	/// Collections created with `create_collection`, and `DEFAULT_COLLECTION` once configured.
+	#[pallet::storage]
+	#[pallet::getter(fn collections)]
+	pub type Collections<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		CollectionId,
+		CollectionInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+	>;
+
+	/// Id of the last collection created. Ids start after `DEFAULT_COLLECTION`.
+	#[pallet::storage]
+	pub type LastCollectionId<T> = StorageValue<_, CollectionId, ValueQuery>;
+
+	/// Accounts allowed to create collections, besides root.
+	#[pallet::storage]
+	#[pallet::getter(fn collection_admins)]
+	pub type CollectionAdmins<T: Config> = StorageMap<_, Blake2_128Concat, T::AccountId, ()>;
+
+	/// Prefix of the token URIs of the kitties in each collection, see `token_uri`.
+	#[pallet::storage]
+	#[pallet::getter(fn base_uri)]
+	pub type BaseUri<T: Config> = StorageMap<_, Blake2_128Concat, CollectionId, BaseUriOf<T>>;
+
+	/// Attributes of each kitty, by namespace and key.
+	#[pallet::storage]
+	#[pallet::getter(fn attributes)]
+	pub type Attributes<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		KittyId,
+		Blake2_128Concat,
+		(AttributeNamespace, AttributeKeyOf<T>),
+		AttributeValueOf<T>,
+	>;
+
+	/// Number of attributes of each kitty, capped at `MaxAttributes`.
+	#[pallet::storage]
+	pub type AttributeCount<T> = StorageMap<_, Blake2_128Concat, KittyId, u32, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn kitties)]
@@ -53,10 +230,113 @@ pub mod pallet {
 	#[pallet::getter(fn kitty_parents)]
 	pub type KittyParents<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, (KittyId, KittyId), OptionQuery>;
 
+	/// Kitties bred from each kitty, oldest first. Only breeds made since this index was added
+	/// are recorded.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_children)]
+	pub type KittyChildren<T: Config> =
+		StorageMap<_, Blake2_128Concat, KittyId, BoundedVec<KittyId, T::MaxChildren>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn kitty_owner)]
 	pub type KittyOwner<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
 
+	/// Account that created or bred the kitty. It receives `CreatorRoyalty` on every resale.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_creator)]
+	pub type KittyCreators<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, T::AccountId>;
+
+	/// Standing offers, reserved from the bidder until accepted or cancelled.
+	#[pallet::storage]
+	#[pallet::getter(fn offers)]
+	pub type Offers<T: Config> = StorageDoubleMap<
+		_,
+		Blake2_128Concat,
+		KittyId,
+		Blake2_128Concat,
+		T::AccountId,
+		Offer<BalanceOf<T>, T::BlockNumber>,
+	>;
+
+	/// Number of standing offers on each kitty, capped at `MaxOffersPerKitty`.
+	#[pallet::storage]
+	#[pallet::getter(fn offer_count)]
+	pub type OfferCount<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, u32, ValueQuery>;
+
+	/// Raw key of `Offers` where the `on_idle` cleanup of expired offers resumes.
+	#[pallet::storage]
+	pub type OfferCleanupCursor<T: Config> = StorageValue<_, BoundedVec<u8, ConstU32<256>>>;
+
+	/// Lease terms proposed by the owner with `lease`, until the lessee accepts them.
+	#[pallet::storage]
+	#[pallet::getter(fn lease_offers)]
+	pub type LeaseOffers<T: Config> =
+		StorageMap<_, Blake2_128Concat, KittyId, LeaseTerms<T::AccountId, BalanceOf<T>, T::BlockNumber>>;
+
+	/// Swaps proposed with `propose_swap`, until accepted or cancelled.
+	#[pallet::storage]
+	#[pallet::getter(fn swaps)]
+	pub type Swaps<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		SwapId,
+		Swap<T::AccountId, BalanceOf<T>, T::BlockNumber, T::MaxSwapKitties>,
+	>;
+
+	/// Id of the next swap proposed.
+	#[pallet::storage]
+	pub type NextSwapId<T> = StorageValue<_, SwapId, ValueQuery>;
+
+	/// Running leases.
+	#[pallet::storage]
+	#[pallet::getter(fn leases)]
+	pub type Leases<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, Lease<T::AccountId, T::BlockNumber>>;
+
+	/// Kitties whose lease ends in the given block.
+	#[pallet::storage]
+	pub type LeaseExpiries<T: Config> =
+		StorageMap<_, Twox64Concat, T::BlockNumber, BoundedVec<KittyId, T::MaxLeasesPerBlock>, ValueQuery>;
+
+	/// Number of shares a fractionalized kitty was split into. The kitty itself is held by the
+	/// pallet account until all shares are redeemed.
+	#[pallet::storage]
+	#[pallet::getter(fn total_shares)]
+	pub type TotalShares<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, Shares>;
+
+	/// Shares of fractionalized kitties held by each account.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_shares)]
+	pub type KittyShares<T: Config> =
+		StorageDoubleMap<_, Blake2_128Concat, KittyId, Blake2_128Concat, T::AccountId, Shares, ValueQuery>;
+
+	/// Kitties entered into the next competition, with the account that gets the prize.
+	#[pallet::storage]
+	#[pallet::getter(fn entrants)]
+	pub type Entrants<T: Config> =
+		StorageValue<_, BoundedVec<(KittyId, T::AccountId), T::MaxEntrants>, ValueQuery>;
+
+	/// Rating of each kitty, raised by every competition it wins.
+	#[pallet::storage]
+	#[pallet::getter(fn kitty_rating)]
+	pub type KittyRatings<T: Config> = StorageMap<_, Blake2_128Concat, KittyId, u32, ValueQuery>;
+
+	/// Mint price of each collection after its last mint or sale, and the block it was set in. It
+	/// decays from there.
+	#[pallet::storage]
+	pub type MintPrice<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionId, (BalanceOf<T>, T::BlockNumber)>;
+
+	/// Floor and ceiling of the mint price of each collection, set by root.
+	#[pallet::storage]
+	#[pallet::getter(fn price_bounds)]
+	pub type PriceBounds<T: Config> =
+		StorageMap<_, Blake2_128Concat, CollectionId, (BalanceOf<T>, BalanceOf<T>)>;
+
+	/// Breeders of the current distribution period, one entry per `breed`.
+	#[pallet::storage]
+	#[pallet::getter(fn period_breeders)]
+	pub type PeriodBreeders<T: Config> = StorageValue<_, BoundedVec<T::AccountId, T::MaxRewardedBreeders>, ValueQuery>;
+
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(super) fn deposit_event)]
 	pub enum Event<T: Config> {
@@ -65,6 +345,55 @@ pub mod pallet {
 		KittyTransferred{ who: T::AccountId, recipient: T::AccountId, kitty_id: KittyId },
 		KittyOnSale {who: T::AccountId, kitty_id: KittyId},
 		KittyBought {who: T::AccountId, kitty_id: KittyId},
+		PriceBoundsSet { collection_id: CollectionId, floor: BalanceOf<T>, ceiling: BalanceOf<T> },
+		PotSpent { recipient: T::AccountId, amount: BalanceOf<T> },
+		PotBurned { amount: BalanceOf<T> },
+		BreederRewardsPaid { breeds: u32, reward: BalanceOf<T> },
+		RoyaltyPaid {
+			kitty_id: KittyId,
+			creator: Option<T::AccountId>,
+			creator_amount: BalanceOf<T>,
+			pot_amount: BalanceOf<T>,
+		},
+		OfferMade { who: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		OfferWithdrawn { who: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		OfferExpired { who: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		LeaseOffered {
+			who: T::AccountId,
+			kitty_id: KittyId,
+			lessee: T::AccountId,
+			duration: T::BlockNumber,
+			fee: BalanceOf<T>,
+		},
+		LeaseStarted { who: T::AccountId, kitty_id: KittyId, expires_at: T::BlockNumber },
+		LeaseEnded { kitty_id: KittyId, lessee: T::AccountId },
+		KittyFractionalized { who: T::AccountId, kitty_id: KittyId, shares: Shares },
+		SharesTransferred { kitty_id: KittyId, from: T::AccountId, to: T::AccountId, amount: Shares },
+		KittyRedeemed { who: T::AccountId, kitty_id: KittyId },
+		KittyEntered { who: T::AccountId, kitty_id: KittyId },
+		CompetitionWon { kitty_id: KittyId, winner: T::AccountId, prize: BalanceOf<T>, entrants: u32 },
+		OfferAccepted { who: T::AccountId, buyer: T::AccountId, kitty_id: KittyId, amount: BalanceOf<T> },
+		CollectionAdminSet { who: T::AccountId, is_admin: bool },
+		CollectionCreated { collection_id: CollectionId, admin: Option<T::AccountId> },
+		CollectionUpdated { collection_id: CollectionId },
+		AllowlistOnlySet { enabled: bool },
+		AllowlistUpdated { who: T::AccountId, allowed: bool },
+		AttributeSet {
+			kitty_id: KittyId,
+			namespace: AttributeNamespace,
+			key: AttributeKeyOf<T>,
+			value: AttributeValueOf<T>,
+		},
+		AttributeCleared { kitty_id: KittyId, namespace: AttributeNamespace, key: AttributeKeyOf<T> },
+		BaseUriSet { collection_id: CollectionId, base_uri: Option<BaseUriOf<T>> },
+		SwapProposed {
+			swap_id: SwapId,
+			proposer: T::AccountId,
+			counterparty: T::AccountId,
+			expiry: T::BlockNumber,
+		},
+		SwapAccepted { swap_id: SwapId, proposer: T::AccountId, counterparty: T::AccountId },
+		SwapCancelled { swap_id: SwapId },
 	}
 
 	#[pallet::error]
@@ -76,6 +405,120 @@ pub mod pallet {
 		AlreadyOnSale,
 		AlreadyOwned,
 		NotOnSale,
+		InvalidPriceBounds,
+		/// The account has no offer on this kitty.
+		NoOffer,
+		/// The offer is past its expiry, or the given expiry is already in the past.
+		OfferExpired,
+		/// The kitty already has `MaxOffersPerKitty` offers.
+		TooManyOffers,
+		/// The kitties share an ancestor within `InbreedingGenerations`.
+		Inbreeding,
+		/// One of the parents already has `MaxChildren` children.
+		TooManyChildren,
+		/// The kitty is leased out and cannot change hands until the lease ends.
+		KittyLeased,
+		/// There is no lease offer for this account on the kitty.
+		NoLeaseOffer,
+		/// A lease must last at least one block.
+		InvalidLeaseDuration,
+		/// Too many leases already end in the same block.
+		TooManyLeases,
+		/// A kitty must be split into at least one share.
+		InvalidShares,
+		/// The kitty is not fractionalized.
+		NotFractionalized,
+		/// The account does not hold enough shares.
+		InsufficientShares,
+		/// The kitty is already entered into the next competition.
+		AlreadyEntered,
+		/// The next competition has `MaxEntrants` kitties already.
+		CompetitionFull,
+		/// The collection does not exist.
+		UnknownCollection,
+		/// The collection reached its supply cap.
+		SupplyExhausted,
+		/// The collection is outside of its mint window.
+		MintWindowClosed,
+		/// The breeding rules of the collections do not allow this pair.
+		BreedingNotAllowed,
+		/// No collection ids are left.
+		NoAvailableCollectionId,
+		/// `MaxSupply` kitties exist already.
+		MaxSupplyReached,
+		/// The account created `MintLimitPerPeriod` kitties in the current period already.
+		MintLimitReached,
+		/// Only allowlisted accounts may create kitties at the moment.
+		NotAllowlisted,
+		/// The kitty is soulbound.
+		NotTransferable,
+		/// The kitty has `MaxAttributes` attributes already.
+		TooManyAttributes,
+		/// The attribute is not set.
+		NoAttribute,
+		/// A swap needs kitties on both sides, each at most once, and another account.
+		InvalidSwap,
+		/// The swap does not exist or is proposed to another account.
+		NoSwap,
+		/// The swap expired.
+		SwapExpired,
+		/// No swap ids are left.
+		NoAvailableSwapId,
+		/// The kitty costs more than the buyer is willing to pay.
+		PriceTooHigh,
+		/// Funds reserved for a purchase were released or slashed in the meantime.
+		ReserveMissing,
+		/// The kitty is fractionalized.
+		Fractionalized,
+		/// The offer is below `MinOffer` or the existential deposit.
+		OfferTooLow,
+		/// The offer expires more than `MaxOfferDuration` blocks from now.
+		OfferTooLong,
+	}
+
+	#[pallet::genesis_config]
+	pub struct GenesisConfig<T: Config> {
+		/// Kitties of `DEFAULT_COLLECTION`, as `(owner, name, dna)`. They get consecutive item ids
+		/// from `next_item_id`; kitties without DNA get one derived from their owner and id.
+		pub kitties: Vec<(T::AccountId, KittyName, Option<KittyDna>)>,
+		/// Item id of the first kitty of `DEFAULT_COLLECTION`.
+		pub next_item_id: ItemId,
+		/// Kitties put on sale, among `kitties`.
+		pub on_sale: Vec<KittyId>,
+	}
+
+	#[cfg(feature = "std")]
+	impl<T: Config> Default for GenesisConfig<T> {
+		fn default() -> Self {
+			Self { kitties: Vec::new(), next_item_id: 0, on_sale: Vec::new() }
+		}
+	}
+
+	#[pallet::genesis_build]
+	impl<T: Config> GenesisBuild<T> for GenesisConfig<T> {
+		fn build(&self) {
+			assert!(
+				self.kitties.len() as u64 <= T::MaxSupply::get() as u64,
+				"more genesis kitties than MaxSupply"
+			);
+
+			let mut item_id = self.next_item_id;
+			for (owner, name, dna) in &self.kitties {
+				let kitty_id = (DEFAULT_COLLECTION, item_id);
+				let dna = dna.unwrap_or_else(|| (owner, kitty_id).using_encoded(blake2_128));
+				Kitties::<T>::insert(kitty_id, Kitty { name: *name, dna });
+				KittyOwner::<T>::insert(kitty_id, owner);
+				KittyCreators::<T>::insert(kitty_id, owner);
+				item_id = item_id.checked_add(1).expect("genesis kitty ids overflow");
+			}
+			NextItemId::<T>::insert(DEFAULT_COLLECTION, item_id);
+			TotalSupply::<T>::put(self.kitties.len() as u32);
+
+			for kitty_id in &self.on_sale {
+				assert!(Kitties::<T>::contains_key(kitty_id), "genesis kitty on sale does not exist");
+				KittyOnSale::<T>::insert(kitty_id, ());
+			}
+		}
 	}
 
 	#[pallet::pallet]
@@ -84,9 +527,31 @@ pub mod pallet {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(n: T::BlockNumber) -> Weight {
+			let mut weight = Self::end_leases(n);
+			if (n % T::DistributionPeriod::get()).is_zero() {
+				weight = weight.saturating_add(Self::distribute_to_breeders());
+			}
+			if (n % T::CompetitionPeriod::get()).is_zero() {
+				weight = weight.saturating_add(Self::run_competition(n));
+			}
+			weight
+		}
+
+		fn on_idle(n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			Self::clean_expired_offers(n, remaining_weight)
+		}
+
 		fn on_runtime_upgrade() -> Weight {
 			upgrade_storage::<T>()
 		}
+
+		fn integrity_test() {
+			// All three are divisors in block hooks or `create`.
+			assert!(!T::DistributionPeriod::get().is_zero(), "`DistributionPeriod` must not be zero");
+			assert!(!T::CompetitionPeriod::get().is_zero(), "`CompetitionPeriod` must not be zero");
+			assert!(!T::MintPeriod::get().is_zero(), "`MintPeriod` must not be zero");
+		}
 	}
 
 
@@ -95,21 +560,33 @@ pub mod pallet {
 		#[pallet::call_index(0)]
 		#[pallet::weight(10_000)]
 		// pub fn create(origin: OriginFor<T>) -> DispatchResult {
-		pub fn create(origin: OriginFor<T>, name: KittyName) -> DispatchResult {
+		pub fn create(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			name: KittyName,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(
+				!Self::allowlist_only() || MintAllowlist::<T>::contains_key(&who),
+				Error::<T>::NotAllowlisted
+			);
+			Self::count_mint(&who)?;
 
-			let kitty_id = Self::get_next_kitty_id()?;
+			let kitty_id = Self::get_next_kitty_id(collection_id)?;
 			// let kitty = Kitty(Self::random_value(&who));
 			let dna = Self::random_value(&who);
 			let kitty = Kitty{ dna, name};
 
-			let price = T::KittyPrice::get();
+			let price = Self::current_price(kitty_id.0);
 			// T::Currency::reserve(&who, price)?;
 			T::Currency::transfer(&who, &Self::get_account_id(), price, ExistenceRequirement::KeepAlive)?;
+			Self::raise_price(kitty_id.0, price);
 
 			Kitties::<T>::insert(kitty_id, &kitty);
 			KittyOwner::<T>::insert(kitty_id, &who);
+			KittyCreators::<T>::insert(kitty_id, &who);
 
+			T::OnKittyEvent::on_created(&who, kitty_id);
 			Self::deposit_event(Event::KittyCreated { who, kitty_id, kitty });
 			Ok(())
 		}
@@ -123,34 +600,50 @@ pub mod pallet {
 
 			ensure!(Kitties::<T>::contains_key(kitty_id_1),Error::<T>::InvalidKittyId);
 			ensure!(Kitties::<T>::contains_key(kitty_id_2),Error::<T>::InvalidKittyId);
+			ensure!(Self::can_use(kitty_id_1, &who), Error::<T>::NotOwner);
+			ensure!(Self::can_use(kitty_id_2, &who), Error::<T>::NotOwner);
+			ensure!(Self::can_breed(kitty_id_1.0, kitty_id_2.0), Error::<T>::BreedingNotAllowed);
+			ensure!(!Self::is_inbreeding(kitty_id_1, kitty_id_2), Error::<T>::Inbreeding);
+			for parent in [kitty_id_1, kitty_id_2] {
+				let children = KittyChildren::<T>::decode_len(parent).unwrap_or(0);
+				ensure!(children < T::MaxChildren::get() as usize, Error::<T>::TooManyChildren);
+			}
 
-			// let kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
-			// let kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
-
-			let kitty_id = Self::get_next_kitty_id().map_err(|_| Error::<T>::InvalidKittyId)?;
+			let kitty_1 = Self::kitties(kitty_id_1).ok_or(Error::<T>::InvalidKittyId)?;
+			let kitty_2 = Self::kitties(kitty_id_2).ok_or(Error::<T>::InvalidKittyId)?;
 
-			// let selector = Self::random_value(&who);
-			
-			// let mut data = [0u8; 16];
-			let dna = [0u8; 16];
+			// The child joins the collection of the first parent.
+			let kitty_id = Self::get_next_kitty_id(kitty_id_1.0)?;
 
-			// for i in 0..kitty_1.0.len(){
-			// 	// 0 choose kitty2, and 1 choose kitty1
-			// 	data[i] = (kitty_1.0[i] &selector[i]) | (kitty_2.0[i] & !selector[i]);
-			// }
+			let selector = Self::random_value(&who);
+			// Each bit set in the selector comes from kitty_1, the others from kitty_2, so the
+			// stats of the child follow its parents.
+			let dna = core::array::from_fn(|i| {
+				(kitty_1.dna[i] & selector[i]) | (kitty_2.dna[i] & !selector[i])
+			});
 
 			// let kitty = Kitty(data);
 			let kitty = Kitty{ dna, name};
 
-			let price = T::KittyPrice::get();
+			let price = Self::current_price(kitty_id.0);
 			// T::Currency::reserve(&who, price)?;
 			T::Currency::transfer(&who, &Self::get_account_id(), price, ExistenceRequirement::KeepAlive)?;
+			Self::raise_price(kitty_id.0, price);
 
 			Kitties::<T>::insert(kitty_id, &kitty);
 			KittyOwner::<T>::insert(kitty_id, &who);
+			KittyCreators::<T>::insert(kitty_id, &who);
 			KittyParents::<T>::insert(kitty_id, (kitty_id_1,kitty_id_2));
-			NextKittyId::<T>::set(kitty_id + 1);
+			for parent in [kitty_id_1, kitty_id_2] {
+				KittyChildren::<T>::mutate(parent, |children| {
+					let _ = children.try_push(kitty_id);
+				});
+			}
+			PeriodBreeders::<T>::mutate(|breeders| {
+				let _ = breeders.try_push(who.clone());
+			});
 
+			T::OnKittyEvent::on_bred(&who, kitty_id, (kitty_id_1, kitty_id_2));
 			Self::deposit_event(Event::KittyBred { who, kitty_id, kitty});
 			Ok(())
 		}
@@ -159,77 +652,825 @@ pub mod pallet {
 		#[pallet::weight(10_000)]
 		pub fn transfer(origin: OriginFor<T>, recipient: T::AccountId, kitty_id: KittyId) -> DispatchResult { 
 			let who = ensure_signed(origin)?;
-			ensure!(Kitties::<T>::contains_key(kitty_id),Error::<T>::InvalidKittyId);
+			Self::do_transfer(who, recipient, kitty_id)
+		}
 
-			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::NotOwner)?;
-			ensure!(owner == who, Error::<T>::NotOwner);
+		#[pallet::call_index(3)]
+		#[pallet::weight(10_000)]
+		pub fn sale(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_sale(who, kitty_id)
+		}
 
 
-			KittyOwner::<T>::insert(kitty_id, &recipient);
-			Self::deposit_event(Event::KittyTransferred { who, recipient, kitty_id});
+		/// Buy a kitty on sale at the current price, failing if it is above `max_price`.
+		#[pallet::call_index(4)]
+		#[pallet::weight(10_000)]
+		pub fn buy(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_buy(who, kitty_id, max_price)
+		}
+
+		/// Set the range the mint price of `collection_id` is kept in.
+		#[pallet::call_index(5)]
+		#[pallet::weight(10_000)]
+		pub fn set_price_bounds(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			floor: BalanceOf<T>,
+			ceiling: BalanceOf<T>,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+			ensure!(floor <= ceiling, Error::<T>::InvalidPriceBounds);
+			ensure!(Self::collection_config(collection_id).is_some(), Error::<T>::UnknownCollection);
+
+			PriceBounds::<T>::insert(collection_id, (floor, ceiling));
+
+			Self::deposit_event(Event::PriceBoundsSet { collection_id, floor, ceiling });
 			Ok(())
 		}
 
-		#[pallet::call_index(3)]
+		/// Pay `amount` out of the pallet account to `recipient`.
+		#[pallet::call_index(6)]
 		#[pallet::weight(10_000)]
-		pub fn sale(
+		pub fn spend_pot(
 			origin: OriginFor<T>,
-			kitty_id: u32,
+			recipient: T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			T::SpendOrigin::ensure_origin(origin)?;
+
+			T::Currency::transfer(&Self::get_account_id(), &recipient, amount, ExistenceRequirement::KeepAlive)?;
+
+			Self::deposit_event(Event::PotSpent { recipient, amount });
+			Ok(())
+		}
+
+		/// Burn `amount` out of the pallet account, reducing the total issuance.
+		#[pallet::call_index(7)]
+		#[pallet::weight(10_000)]
+		pub fn burn_pot(origin: OriginFor<T>, amount: BalanceOf<T>) -> DispatchResult {
+			T::SpendOrigin::ensure_origin(origin)?;
+
+			let _ = T::Currency::withdraw(
+				&Self::get_account_id(),
+				amount,
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::KeepAlive,
+			)?;
+
+			Self::deposit_event(Event::PotBurned { amount });
+			Ok(())
+		}
+
+		/// Offer `amount` for a kitty, listed or not, until block `expiry`. The amount stays
+		/// reserved until the owner accepts the offer, the bidder withdraws it or it expires. A new
+		/// offer replaces the previous one.
+		#[pallet::call_index(8)]
+		#[pallet::weight(10_000)]
+		pub fn make_offer(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			amount: BalanceOf<T>,
+			expiry: T::BlockNumber,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			
-			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			ensure!(!TotalShares::<T>::contains_key(kitty_id), Error::<T>::Fractionalized);
+			ensure!(Self::kitty_owner(kitty_id) != Some(who.clone()), Error::<T>::AlreadyOwned);
+			ensure!(Self::is_transferable(kitty_id), Error::<T>::NotTransferable);
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(expiry >= now, Error::<T>::OfferExpired);
+			ensure!(expiry - now <= T::MaxOfferDuration::get(), Error::<T>::OfferTooLong);
+			let min_offer = T::MinOffer::get().max(T::Currency::minimum_balance());
+			ensure!(amount >= min_offer, Error::<T>::OfferTooLow);
+
+			match Offers::<T>::take(kitty_id, &who) {
+				Some(previous) => {
+					T::Currency::unreserve(&who, previous.amount);
+				},
+				None => OfferCount::<T>::try_mutate(kitty_id, |count| -> DispatchResult {
+					ensure!(*count < T::MaxOffersPerKitty::get(), Error::<T>::TooManyOffers);
+					*count += 1;
+					Ok(())
+				})?,
+			}
+			T::Currency::reserve(&who, amount)?;
+			Offers::<T>::insert(kitty_id, &who, Offer { amount, expiry });
+
+			T::OnKittyEvent::on_offer_made(&who, kitty_id, amount);
+			Self::deposit_event(Event::OfferMade { who, kitty_id, amount });
+			Ok(())
+		}
+
+		/// Withdraw an offer and release its reserve.
+		#[pallet::call_index(9)]
+		#[pallet::weight(10_000)]
+		pub fn withdraw_offer(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let amount = Self::remove_offer(kitty_id, &who).ok_or(Error::<T>::NoOffer)?;
+
+			Self::deposit_event(Event::OfferWithdrawn { who, kitty_id, amount });
+			Ok(())
+		}
 
+		/// Sell a kitty to `buyer` for the amount they offered.
+		#[pallet::call_index(10)]
+		#[pallet::weight(10_000)]
+		pub fn accept_offer(origin: OriginFor<T>, kitty_id: KittyId, buyer: T::AccountId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
-			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(buyer != who, Error::<T>::AlreadyOwned);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(Self::is_transferable(kitty_id), Error::<T>::NotTransferable);
+			let offer = Offers::<T>::get(kitty_id, &buyer).ok_or(Error::<T>::NoOffer)?;
+			ensure!(offer.expiry >= <frame_system::Pallet<T>>::block_number(), Error::<T>::OfferExpired);
 
-			<KittyOnSale<T>>::insert(kitty_id, ());
-			Self::deposit_event(Event::KittyOnSale { who, kitty_id});
+			Offers::<T>::remove(kitty_id, &buyer);
+			OfferCount::<T>::mutate(kitty_id, |count| *count = count.saturating_sub(1));
+			let amount = offer.amount;
+			Self::settle(kitty_id, &who, amount, |to, amount| {
+				Self::pay_reserved(&buyer, to, amount)
+			})?;
+
+			Self::set_owner(kitty_id, &buyer);
+			Self::record_sale(kitty_id.0, amount);
 
+			T::OnKittyEvent::on_sold(&who, &buyer, kitty_id, amount);
+			Self::deposit_event(Event::OfferAccepted { who, buyer, kitty_id, amount });
 			Ok(())
 		}
 
+		/// Propose to lease a kitty to `lessee` for `duration` blocks against `fee`. The lease
+		/// starts once the lessee accepts it with `accept_lease`. New terms replace earlier ones.
+		#[pallet::call_index(11)]
+		#[pallet::weight(10_000)]
+		pub fn lease(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			lessee: T::AccountId,
+			duration: T::BlockNumber,
+			fee: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!TotalShares::<T>::contains_key(kitty_id), Error::<T>::Fractionalized);
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(lessee != who, Error::<T>::AlreadyOwned);
+			ensure!(!duration.is_zero(), Error::<T>::InvalidLeaseDuration);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
 
-		#[pallet::call_index(4)]
+			LeaseOffers::<T>::insert(
+				kitty_id,
+				LeaseTerms { lessor: who.clone(), lessee: lessee.clone(), duration, fee },
+			);
+
+			T::OnKittyEvent::on_lease_offered(&who, &lessee, kitty_id);
+			Self::deposit_event(Event::LeaseOffered { who, kitty_id, lessee, duration, fee });
+			Ok(())
+		}
+
+		/// Accept the lease proposed for a kitty, paying the fee to its owner.
+		#[pallet::call_index(12)]
 		#[pallet::weight(10_000)]
-		pub fn buy(
+		pub fn accept_lease(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let terms = LeaseOffers::<T>::get(kitty_id)
+				.filter(|terms| terms.lessee == who)
+				.ok_or(Error::<T>::NoLeaseOffer)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(terms.lessor.clone()), Error::<T>::NoLeaseOffer);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+
+			let expires_at = <frame_system::Pallet<T>>::block_number().saturating_add(terms.duration);
+			LeaseExpiries::<T>::try_mutate(expires_at, |kitties| kitties.try_push(kitty_id))
+				.map_err(|_| Error::<T>::TooManyLeases)?;
+			T::Currency::transfer(&who, &terms.lessor, terms.fee, ExistenceRequirement::KeepAlive)?;
+
+			LeaseOffers::<T>::remove(kitty_id);
+			Leases::<T>::insert(kitty_id, Lease { lessee: who.clone(), expires_at });
+			KittyOnSale::<T>::remove(kitty_id);
+
+			T::OnKittyEvent::on_leased(&terms.lessor, &who, kitty_id);
+			Self::deposit_event(Event::LeaseStarted { who, kitty_id, expires_at });
+			Ok(())
+		}
+
+		/// Lock a kitty in its vault account and mint `shares` shares of it to the owner.
+		#[pallet::call_index(13)]
+		#[pallet::weight(10_000)]
+		pub fn fractionalize(origin: OriginFor<T>, kitty_id: KittyId, shares: Shares) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(Self::is_transferable(kitty_id), Error::<T>::NotTransferable);
+			ensure!(!shares.is_zero(), Error::<T>::InvalidShares);
+
+			Self::set_owner(kitty_id, &Self::vault_account_id(kitty_id));
+			TotalShares::<T>::insert(kitty_id, shares);
+			KittyShares::<T>::insert(kitty_id, &who, shares);
+
+			T::OnKittyEvent::on_fractionalized(&who, kitty_id, shares);
+			Self::deposit_event(Event::KittyFractionalized { who, kitty_id, shares });
+			Ok(())
+		}
+
+		/// Send `amount` shares of a fractionalized kitty to `recipient`.
+		#[pallet::call_index(14)]
+		#[pallet::weight(10_000)]
+		pub fn transfer_shares(
 			origin: OriginFor<T>,
-			kitty_id: u32,
+			kitty_id: KittyId,
+			recipient: T::AccountId,
+			amount: Shares,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
+			ensure!(TotalShares::<T>::contains_key(kitty_id), Error::<T>::NotFractionalized);
 
-			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+			Self::transfer_shares_between(kitty_id, &who, &recipient, amount)?;
 
-			let owner = Self::kitty_owner(kitty_id).ok_or::<DispatchError>(Error::<T>::NoOwner.into())?;
-			ensure!(owner != who, Error::<T>::AlreadyOwned);
-			ensure!(Self::kitty_on_sale(kitty_id).is_some(), Error::<T>::NotOnSale);
+			T::OnKittyEvent::on_shares_transferred(&who, &recipient, kitty_id, amount);
+			Self::deposit_event(Event::SharesTransferred { kitty_id, from: who, to: recipient, amount });
+			Ok(())
+		}
 
-			let price = T::KittyPrice::get();
-			// T::Currency::reserve(&who, price)?;
-			// T::Currency::unreserve(&owner, price)?;
-			T::Currency::transfer(&who, &owner, price, ExistenceRequirement::KeepAlive)?;
-			
-			<KittyOwner<T>>::insert(kitty_id, &who);
-			<KittyOnSale<T>>::remove(kitty_id);
+		/// Burn all shares of a fractionalized kitty and take it out of the pallet account.
+		/// The caller must hold every share.
+		#[pallet::call_index(15)]
+		#[pallet::weight(10_000)]
+		pub fn redeem(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let total = TotalShares::<T>::get(kitty_id).ok_or(Error::<T>::NotFractionalized)?;
+			ensure!(KittyShares::<T>::get(kitty_id, &who) == total, Error::<T>::InsufficientShares);
 
-			Self::deposit_event(Event::KittyBought { who, kitty_id});
+			TotalShares::<T>::remove(kitty_id);
+			KittyShares::<T>::remove(kitty_id, &who);
+			Self::set_owner(kitty_id, &who);
+
+			T::OnKittyEvent::on_redeemed(&who, kitty_id);
+			Self::deposit_event(Event::KittyRedeemed { who, kitty_id });
+			Ok(())
+		}
+
+		/// Enter a kitty into the next competition, paying `EntryFee` into the prize pool. The
+		/// owner, or the lessee of a leased kitty, receives the prize if it wins. The entry, but
+		/// not the fee, is dropped if the kitty changes owner or the lease ends before the
+		/// competition.
+		#[pallet::call_index(16)]
+		#[pallet::weight(10_000)]
+		pub fn enter_competition(origin: OriginFor<T>, kitty_id: KittyId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::can_use(kitty_id, &who), Error::<T>::NotOwner);
+
+			let mut entrants = Entrants::<T>::get();
+			ensure!(!entrants.iter().any(|(entered, _)| *entered == kitty_id), Error::<T>::AlreadyEntered);
+			entrants.try_push((kitty_id, who.clone())).map_err(|_| Error::<T>::CompetitionFull)?;
+
+			T::Currency::transfer(
+				&who,
+				&Self::arena_account_id(),
+				T::EntryFee::get(),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Entrants::<T>::put(entrants);
+
+			T::OnKittyEvent::on_entered(&who, kitty_id);
+			Self::deposit_event(Event::KittyEntered { who, kitty_id });
+			Ok(())
+		}
 
+		/// Allow or disallow an account to create collections.
+		#[pallet::call_index(17)]
+		#[pallet::weight(10_000)]
+		pub fn set_collection_admin(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			is_admin: bool,
+		) -> DispatchResult {
+			ensure_root(origin)?;
+
+			if is_admin {
+				CollectionAdmins::<T>::insert(&who, ());
+			} else {
+				CollectionAdmins::<T>::remove(&who);
+			}
+
+			Self::deposit_event(Event::CollectionAdminSet { who, is_admin });
 			Ok(())
 		}
 
-	}	
+		/// Start a new collection. Callable by root or a collection admin.
+		#[pallet::call_index(18)]
+		#[pallet::weight(10_000)]
+		pub fn create_collection(
+			origin: OriginFor<T>,
+			config: CollectionConfig<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			let admin = Self::ensure_collection_origin(origin)?;
+
+			let collection_id = LastCollectionId::<T>::try_mutate(|last_id| -> Result<_, DispatchError> {
+				*last_id = last_id.checked_add(1).ok_or(Error::<T>::NoAvailableCollectionId)?;
+				Ok(*last_id)
+			})?;
+			Collections::<T>::insert(collection_id, CollectionInfo { admin: admin.clone(), config });
+
+			T::OnKittyEvent::on_collection_created(collection_id, admin.as_ref());
+			Self::deposit_event(Event::CollectionCreated { collection_id, admin });
+			Ok(())
+		}
+
+		/// Change the settings of a collection. Callable by root, or by the admin that created
+		/// it. Only root can configure `DEFAULT_COLLECTION`.
+		#[pallet::call_index(19)]
+		#[pallet::weight(10_000)]
+		pub fn update_collection(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			config: CollectionConfig<BalanceOf<T>, T::BlockNumber>,
+		) -> DispatchResult {
+			let who = Self::ensure_collection_origin(origin)?;
+			ensure!(Self::collection_config(collection_id).is_some(), Error::<T>::UnknownCollection);
+
+			Collections::<T>::try_mutate(collection_id, |info| -> DispatchResult {
+				let admin = info.as_ref().and_then(|info| info.admin.clone());
+				ensure!(who.is_none() || who == admin, DispatchError::BadOrigin);
+				*info = Some(CollectionInfo { admin, config });
+				Ok(())
+			})?;
+
+			T::OnKittyEvent::on_collection_updated(collection_id);
+			Self::deposit_event(Event::CollectionUpdated { collection_id });
+			Ok(())
+		}
+
+		/// Restrict `create` to allowlisted accounts, or open it to everyone again.
+		#[pallet::call_index(20)]
+		#[pallet::weight(10_000)]
+		pub fn set_allowlist_only(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			T::MintOrigin::ensure_origin(origin)?;
+
+			AllowlistOnly::<T>::put(enabled);
+
+			Self::deposit_event(Event::AllowlistOnlySet { enabled });
+			Ok(())
+		}
+
+		/// Add an account to the mint allowlist, or remove it.
+		#[pallet::call_index(21)]
+		#[pallet::weight(10_000)]
+		pub fn set_allowlisted(
+			origin: OriginFor<T>,
+			who: T::AccountId,
+			allowed: bool,
+		) -> DispatchResult {
+			T::MintOrigin::ensure_origin(origin)?;
+
+			if allowed {
+				MintAllowlist::<T>::insert(&who, ());
+			} else {
+				MintAllowlist::<T>::remove(&who);
+			}
+
+			Self::deposit_event(Event::AllowlistUpdated { who, allowed });
+			Ok(())
+		}
+
+		/// Create a soulbound kitty for `owner`, e.g. as an achievement. It is free, does not count
+		/// towards the mint limits of `owner` and can never be transferred, sold or fractionalized.
+		#[pallet::call_index(22)]
+		#[pallet::weight(10_000)]
+		pub fn create_soulbound(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			collection_id: CollectionId,
+			name: KittyName,
+		) -> DispatchResult {
+			T::SoulboundOrigin::ensure_origin(origin)?;
+
+			let kitty_id = Self::get_next_kitty_id(collection_id)?;
+			let dna = Self::random_value(&owner);
+			let kitty = Kitty { dna, name };
+
+			Kitties::<T>::insert(kitty_id, &kitty);
+			KittyOwner::<T>::insert(kitty_id, &owner);
+			KittyCreators::<T>::insert(kitty_id, &owner);
+			NonTransferable::<T>::insert(kitty_id, ());
+
+			T::OnKittyEvent::on_created(&owner, kitty_id);
+			Self::deposit_event(Event::KittyCreated { who: owner, kitty_id, kitty });
+			Ok(())
+		}
+
+		/// Set an attribute of a kitty. `Owner` attributes are set by the owner of the kitty,
+		/// `Issuer` attributes by root or the admin that created its collection.
+		#[pallet::call_index(23)]
+		#[pallet::weight(10_000)]
+		pub fn set_attribute(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			namespace: AttributeNamespace,
+			key: AttributeKeyOf<T>,
+			value: AttributeValueOf<T>,
+		) -> DispatchResult {
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			Self::ensure_attribute_origin(origin, kitty_id, namespace)?;
+
+			let attribute = (namespace, key.clone());
+			if !Attributes::<T>::contains_key(kitty_id, &attribute) {
+				AttributeCount::<T>::try_mutate(kitty_id, |count| -> DispatchResult {
+					ensure!(*count < T::MaxAttributes::get(), Error::<T>::TooManyAttributes);
+					*count += 1;
+					Ok(())
+				})?;
+			}
+			Attributes::<T>::insert(kitty_id, attribute, &value);
+
+			T::OnKittyEvent::on_attribute_set(kitty_id, namespace, &key);
+			Self::deposit_event(Event::AttributeSet { kitty_id, namespace, key, value });
+			Ok(())
+		}
+
+		/// Remove an attribute of a kitty. Same permissions as `set_attribute`.
+		#[pallet::call_index(24)]
+		#[pallet::weight(10_000)]
+		pub fn clear_attribute(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			namespace: AttributeNamespace,
+			key: AttributeKeyOf<T>,
+		) -> DispatchResult {
+			ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+			Self::ensure_attribute_origin(origin, kitty_id, namespace)?;
+
+			Attributes::<T>::take(kitty_id, (namespace, key.clone())).ok_or(Error::<T>::NoAttribute)?;
+			AttributeCount::<T>::mutate_exists(kitty_id, |count| {
+				*count = count.map(|count| count.saturating_sub(1)).filter(|count| *count > 0);
+			});
+
+			T::OnKittyEvent::on_attribute_cleared(kitty_id, namespace, &key);
+			Self::deposit_event(Event::AttributeCleared { kitty_id, namespace, key });
+			Ok(())
+		}
+
+		/// Set or remove the base URI of a collection. Callable by root, or by the admin that
+		/// created it.
+		#[pallet::call_index(25)]
+		#[pallet::weight(10_000)]
+		pub fn set_base_uri(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			base_uri: Option<BaseUriOf<T>>,
+		) -> DispatchResult {
+			Self::ensure_issuer(origin, collection_id)?;
+
+			match &base_uri {
+				Some(base_uri) => BaseUri::<T>::insert(collection_id, base_uri),
+				None => BaseUri::<T>::remove(collection_id),
+			}
+
+			Self::deposit_event(Event::BaseUriSet { collection_id, base_uri });
+			Ok(())
+		}
+
+		/// `transfer` several kitties at once. Either all transfers succeed or none does.
+		#[pallet::call_index(26)]
+		#[pallet::weight(
+			10_000u64.saturating_add(10_000u64.saturating_mul(transfers.len() as u64))
+		)]
+		pub fn transfer_batch(
+			origin: OriginFor<T>,
+			transfers: BoundedVec<(T::AccountId, KittyId), T::MaxBatchSize>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			for (recipient, kitty_id) in transfers {
+				Self::do_transfer(who.clone(), recipient, kitty_id)?;
+			}
+			Ok(())
+		}
+
+		/// Put several kitties on sale at once. Either all of them are put on sale or none is.
+		#[pallet::call_index(27)]
+		#[pallet::weight(
+			10_000u64.saturating_add(10_000u64.saturating_mul(kitty_ids.len() as u64))
+		)]
+		pub fn sale_batch(
+			origin: OriginFor<T>,
+			kitty_ids: BoundedVec<KittyId, T::MaxBatchSize>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			for kitty_id in kitty_ids {
+				Self::do_sale(who.clone(), kitty_id)?;
+			}
+			Ok(())
+		}
+
+		/// `buy` several kitties at once, each with its `max_price`. Either all of them are bought
+		/// or none is.
+		#[pallet::call_index(28)]
+		#[pallet::weight(
+			10_000u64.saturating_add(10_000u64.saturating_mul(purchases.len() as u64))
+		)]
+		pub fn buy_batch(
+			origin: OriginFor<T>,
+			purchases: BoundedVec<(KittyId, BalanceOf<T>), T::MaxBatchSize>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			for (kitty_id, max_price) in purchases {
+				Self::do_buy(who.clone(), kitty_id, max_price)?;
+			}
+			Ok(())
+		}
+
+		/// Propose to trade `offered` kitties, plus `top_up` if any, for the `wanted` kitties of
+		/// `counterparty`. The swap can be accepted until block `expiry`.
+		#[pallet::call_index(29)]
+		#[pallet::weight(10_000)]
+		pub fn propose_swap(
+			origin: OriginFor<T>,
+			offered: BoundedVec<KittyId, T::MaxSwapKitties>,
+			wanted: BoundedVec<KittyId, T::MaxSwapKitties>,
+			counterparty: T::AccountId,
+			top_up: Option<BalanceOf<T>>,
+			expiry: T::BlockNumber,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(counterparty != who, Error::<T>::InvalidSwap);
+			ensure!(!offered.is_empty() && !wanted.is_empty(), Error::<T>::InvalidSwap);
+			let distinct = offered.iter().chain(wanted.iter()).collect::<BTreeSet<_>>();
+			ensure!(distinct.len() == offered.len() + wanted.len(), Error::<T>::InvalidSwap);
+			ensure!(expiry >= <frame_system::Pallet<T>>::block_number(), Error::<T>::SwapExpired);
+			Self::ensure_swappable(&offered, &who)?;
+			Self::ensure_swappable(&wanted, &counterparty)?;
+
+			let top_up = top_up.unwrap_or_else(Zero::zero);
+			T::Currency::reserve(&who, top_up)?;
+			let swap_id = NextSwapId::<T>::try_mutate(|next_id| -> Result<_, DispatchError> {
+				let swap_id = *next_id;
+				*next_id = next_id.checked_add(1).ok_or(Error::<T>::NoAvailableSwapId)?;
+				Ok(swap_id)
+			})?;
+			Swaps::<T>::insert(
+				swap_id,
+				Swap {
+					proposer: who.clone(),
+					counterparty: counterparty.clone(),
+					offered,
+					wanted,
+					top_up,
+					expiry,
+				},
+			);
+
+			T::OnKittyEvent::on_swap_proposed(&who, &counterparty, swap_id);
+			Self::deposit_event(Event::SwapProposed {
+				swap_id,
+				proposer: who,
+				counterparty,
+				expiry,
+			});
+			Ok(())
+		}
+
+		/// Accept a swap proposed to the caller, exchanging the kitties and paying the top-up.
+		/// Kitties on sale are taken off sale.
+		#[pallet::call_index(30)]
+		#[pallet::weight(10_000)]
+		pub fn accept_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let swap = Swaps::<T>::get(swap_id)
+				.filter(|swap| swap.counterparty == who)
+				.ok_or(Error::<T>::NoSwap)?;
+			ensure!(swap.expiry >= <frame_system::Pallet<T>>::block_number(), Error::<T>::SwapExpired);
+			Self::ensure_swappable(&swap.offered, &swap.proposer)?;
+			Self::ensure_swappable(&swap.wanted, &who)?;
+
+			Swaps::<T>::remove(swap_id);
+			let missing = T::Currency::unreserve(&swap.proposer, swap.top_up);
+			ensure!(missing.is_zero(), Error::<T>::ReserveMissing);
+			T::Currency::transfer(&swap.proposer, &who, swap.top_up, ExistenceRequirement::AllowDeath)?;
+			for kitty_id in swap.offered.iter() {
+				Self::set_owner(*kitty_id, &who);
+			}
+			for kitty_id in swap.wanted.iter() {
+				Self::set_owner(*kitty_id, &swap.proposer);
+			}
+
+			T::OnKittyEvent::on_swapped(
+				&swap.proposer,
+				&who,
+				&swap.offered,
+				&swap.wanted,
+				swap.top_up,
+			);
+			Self::deposit_event(Event::SwapAccepted {
+				swap_id,
+				proposer: swap.proposer,
+				counterparty: who,
+			});
+			Ok(())
+		}
+
+		/// Cancel a swap and release the top-up. Callable by both parties, and by anyone once the
+		/// swap expired.
+		#[pallet::call_index(31)]
+		#[pallet::weight(10_000)]
+		pub fn cancel_swap(origin: OriginFor<T>, swap_id: SwapId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let swap = Swaps::<T>::get(swap_id).ok_or(Error::<T>::NoSwap)?;
+			ensure!(
+				who == swap.proposer ||
+					who == swap.counterparty ||
+					swap.expiry < <frame_system::Pallet<T>>::block_number(),
+				DispatchError::BadOrigin
+			);
+
+			Swaps::<T>::remove(swap_id);
+			T::Currency::unreserve(&swap.proposer, swap.top_up);
+
+			T::OnKittyEvent::on_swap_cancelled(&swap.proposer, &swap.counterparty, swap_id);
+			Self::deposit_event(Event::SwapCancelled { swap_id });
+			Ok(())
+		}
+	}
 
 	impl<T: Config> Pallet<T> {
-		fn get_next_kitty_id() -> Result<KittyId, DispatchError> {
-			NextKittyId::<T>::try_mutate(|next_id| -> Result<KittyId, DispatchError> {
+		/// Reserve the next item of a collection, if its supply cap and mint window allow it.
+		fn get_next_kitty_id(collection_id: CollectionId) -> Result<KittyId, DispatchError> {
+			let config =
+				Self::collection_config(collection_id).ok_or(Error::<T>::UnknownCollection)?;
+			if let Some((start, end)) = config.mint_window {
+				let now = <frame_system::Pallet<T>>::block_number();
+				ensure!(start <= now && now <= end, Error::<T>::MintWindowClosed);
+			}
+
+			TotalSupply::<T>::try_mutate(|supply| -> DispatchResult {
+				ensure!(*supply < T::MaxSupply::get(), Error::<T>::MaxSupplyReached);
+				*supply += 1;
+				Ok(())
+			})?;
+
+			NextItemId::<T>::try_mutate(collection_id, |next_id| -> Result<KittyId, DispatchError> {
 				let current_id = *next_id;
+				ensure!(
+					config.max_supply.map_or(true, |max| current_id < max),
+					Error::<T>::SupplyExhausted
+				);
 				*next_id = next_id
 					.checked_add(1)
 					.ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
-				Ok(current_id)
+				Ok((collection_id, current_id))
+			})
+		}
+
+		/// Count a `create` against the `MintLimitPerPeriod` of `who`.
+		fn count_mint(who: &T::AccountId) -> DispatchResult {
+			let now = <frame_system::Pallet<T>>::block_number();
+			let period = now / T::MintPeriod::get();
+			AccountMints::<T>::try_mutate(who, |(last_period, minted)| {
+				if *last_period != period {
+					*last_period = period;
+					*minted = 0;
+				}
+				ensure!(*minted < T::MintLimitPerPeriod::get(), Error::<T>::MintLimitReached);
+				*minted += 1;
+				Ok(())
+			})
+		}
+
+		/// Settings of a collection. `DEFAULT_COLLECTION` exists with `KittyPrice` and no limits
+		/// until root configures it.
+		pub fn collection_config(
+			collection_id: CollectionId,
+		) -> Option<CollectionConfig<BalanceOf<T>, T::BlockNumber>> {
+			Collections::<T>::get(collection_id).map(|info| info.config).or_else(|| {
+				(collection_id == DEFAULT_COLLECTION).then(|| CollectionConfig {
+					mint_price: T::KittyPrice::get(),
+					max_supply: None,
+					mint_window: None,
+					breeding: BreedingRule::Open,
+				})
 			})
 		}
 
+		/// Whether kitties of the two collections may breed together.
+		fn can_breed(collection_1: CollectionId, collection_2: CollectionId) -> bool {
+			let allows = |collection_id, other| match Self::collection_config(collection_id) {
+				Some(config) => match config.breeding {
+					BreedingRule::Disabled => false,
+					BreedingRule::SameCollection => collection_id == other,
+					BreedingRule::Open => true,
+				},
+				None => false,
+			};
+			allows(collection_1, collection_2) && allows(collection_2, collection_1)
+		}
+
+		/// Root, or a signed collection admin returned as `Some`.
+		fn ensure_collection_origin(
+			origin: OriginFor<T>,
+		) -> Result<Option<T::AccountId>, DispatchError> {
+			if ensure_root(origin.clone()).is_ok() {
+				return Ok(None)
+			}
+			let who = ensure_signed(origin)?;
+			ensure!(CollectionAdmins::<T>::contains_key(&who), DispatchError::BadOrigin);
+			Ok(Some(who))
+		}
+
+		fn do_transfer(who: T::AccountId, recipient: T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			ensure!(Kitties::<T>::contains_key(kitty_id),Error::<T>::InvalidKittyId);
+
+			let owner = Self::kitty_owner(kitty_id).ok_or(Error::<T>::NotOwner)?;
+			ensure!(owner == who, Error::<T>::NotOwner);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(Self::is_transferable(kitty_id), Error::<T>::NotTransferable);
+
+			Self::set_owner(kitty_id, &recipient);
+			Self::deposit_event(Event::KittyTransferred { who, recipient, kitty_id});
+			Ok(())
+		}
+
+		fn do_sale(who: T::AccountId, kitty_id: KittyId) -> DispatchResult {
+			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+			ensure!(!TotalShares::<T>::contains_key(kitty_id), Error::<T>::Fractionalized);
+
+			ensure!(Self::kitty_owner(kitty_id) == Some(who.clone()), Error::<T>::NotOwner);
+			ensure!(Self::kitty_on_sale(kitty_id).is_none(), Error::<T>::AlreadyOnSale);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(Self::is_transferable(kitty_id), Error::<T>::NotTransferable);
+
+			<KittyOnSale<T>>::insert(kitty_id, ());
+			T::OnKittyEvent::on_listed(&who, kitty_id);
+			Self::deposit_event(Event::KittyOnSale { who, kitty_id});
+
+			Ok(())
+		}
+
+		fn do_buy(who: T::AccountId, kitty_id: KittyId, max_price: BalanceOf<T>) -> DispatchResult {
+			Self::kitties(kitty_id).ok_or::<DispatchError>(Error::<T>::InvalidKittyId.into())?;
+
+			let owner = Self::kitty_owner(kitty_id).ok_or::<DispatchError>(Error::<T>::NoOwner.into())?;
+			ensure!(owner != who, Error::<T>::AlreadyOwned);
+			ensure!(Self::kitty_on_sale(kitty_id).is_some(), Error::<T>::NotOnSale);
+			ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+			ensure!(Self::is_transferable(kitty_id), Error::<T>::NotTransferable);
+
+			let price = Self::current_price(kitty_id.0);
+			ensure!(price <= max_price, Error::<T>::PriceTooHigh);
+			Self::settle(kitty_id, &owner, price, |to, amount| {
+				T::Currency::transfer(&who, to, amount, ExistenceRequirement::KeepAlive)
+			})?;
+
+			Self::set_owner(kitty_id, &who);
+			Self::record_sale(kitty_id.0, price);
+
+			T::OnKittyEvent::on_sold(&owner, &who, kitty_id, price);
+			Self::deposit_event(Event::KittyBought { who, kitty_id});
+
+			Ok(())
+		}
+
+		/// Ensure `owner` can trade away all of `kitty_ids`.
+		fn ensure_swappable(kitty_ids: &[KittyId], owner: &T::AccountId) -> DispatchResult {
+			for kitty_id in kitty_ids {
+				ensure!(Kitties::<T>::contains_key(kitty_id), Error::<T>::InvalidKittyId);
+				ensure!(Self::kitty_owner(kitty_id).as_ref() == Some(owner), Error::<T>::NotOwner);
+				ensure!(!Leases::<T>::contains_key(kitty_id), Error::<T>::KittyLeased);
+				ensure!(Self::is_transferable(*kitty_id), Error::<T>::NotTransferable);
+			}
+			Ok(())
+		}
+
+		/// Ensure `origin` is root or the admin that created `collection_id`.
+		fn ensure_issuer(origin: OriginFor<T>, collection_id: CollectionId) -> DispatchResult {
+			let who = Self::ensure_collection_origin(origin)?;
+			ensure!(Self::collection_config(collection_id).is_some(), Error::<T>::UnknownCollection);
+			let admin = Self::collections(collection_id).and_then(|info| info.admin);
+			ensure!(who.is_none() || who == admin, DispatchError::BadOrigin);
+			Ok(())
+		}
+
+		/// Ensure `origin` may edit the attributes of a kitty in `namespace`.
+		fn ensure_attribute_origin(
+			origin: OriginFor<T>,
+			kitty_id: KittyId,
+			namespace: AttributeNamespace,
+		) -> DispatchResult {
+			match namespace {
+				AttributeNamespace::Owner => {
+					let who = ensure_signed(origin)?;
+					ensure!(Self::kitty_owner(kitty_id) == Some(who), Error::<T>::NotOwner);
+					Ok(())
+				},
+				AttributeNamespace::Issuer => Self::ensure_issuer(origin, kitty_id.0),
+			}
+		}
+
 		fn random_value(sender: &T::AccountId) -> [u8;16] {
 			let payload = (
 				T::Randomness::random_seed(),
@@ -242,5 +1483,382 @@ pub mod pallet {
 		fn get_account_id() -> T::AccountId {
 			T::PalletId::get().into_account_truncating()
 		}
+
+		/// Account holding the prize pool of the next competition, apart from the pot.
+		fn arena_account_id() -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(b"arena")
+		}
+
+		/// Account holding a fractionalized kitty, apart from the pot.
+		pub fn vault_account_id(kitty_id: KittyId) -> T::AccountId {
+			T::PalletId::get().into_sub_account_truncating(kitty_id)
+		}
+
+		/// Resolve the competition of block `now`. Each kitty scores its DNA power plus a random
+		/// roll up to `KittyStats::MAX_POWER`, so stats and luck weigh the same; the best score
+		/// takes the whole prize pool and gains one rating point per opponent. Entrants wait for
+		/// the next competition until there are at least two.
+		fn run_competition(now: T::BlockNumber) -> Weight {
+			let entrants = Entrants::<T>::get();
+			let count = entrants.len() as u32;
+			if count < 2 {
+				return T::DbWeight::get().reads(1)
+			}
+
+			let (seed, _) = T::Randomness::random(&(b"arena", now).encode());
+			let winner = entrants
+				.iter()
+				.enumerate()
+				.max_by_key(|(index, (kitty_id, _))| {
+					let power = Self::kitties(kitty_id).map(|kitty| kitty.stats().power()).unwrap_or(0);
+					let roll = blake2_128(&(&seed, index).encode());
+					let roll = u16::from_le_bytes([roll[0], roll[1]]) as u32;
+					power + roll % (KittyStats::MAX_POWER + 1)
+				})
+				.map(|(_, entrant)| entrant.clone());
+
+			Entrants::<T>::kill();
+			if let Some((kitty_id, winner)) = winner {
+				let arena = Self::arena_account_id();
+				let prize = T::Currency::free_balance(&arena);
+				let _ = T::Currency::transfer(&arena, &winner, prize, ExistenceRequirement::AllowDeath);
+				let rating = KittyRatings::<T>::mutate(kitty_id, |rating| {
+					*rating = rating.saturating_add(count - 1);
+					*rating
+				});
+				T::OnKittyEvent::on_competition_won(&winner, kitty_id, prize, rating);
+				Self::deposit_event(Event::CompetitionWon { kitty_id, winner, prize, entrants: count });
+			}
+
+			T::DbWeight::get().reads_writes(3 + count as u64, 4)
+		}
+
+		/// Price of the next `create` or `breed` in a collection, also charged by `buy` for its
+		/// kitties.
+		///
+		/// Every mint raises the price by `PriceIncrease`, every sale moves it `SalePriceWeight` of
+		/// the way to the sale price, and every block without either lowers it by `PriceDecay`. The
+		/// price so follows both minting demand and the secondary market, within the
+		/// `PriceBounds` of the collection. Without bounds, the floor is its mint price.
+		pub fn current_price(collection_id: CollectionId) -> BalanceOf<T> {
+			let base = Self::collection_config(collection_id)
+				.map_or_else(T::KittyPrice::get, |config| config.mint_price);
+			let (floor, ceiling) = PriceBounds::<T>::get(collection_id)
+				.unwrap_or_else(|| (base, BalanceOf::<T>::max_value()));
+
+			let price = match MintPrice::<T>::get(collection_id) {
+				Some((price, since)) => {
+					let now = <frame_system::Pallet<T>>::block_number();
+					let elapsed: u32 = now.saturating_sub(since).unique_saturated_into();
+					let kept = Permill::one().saturating_sub(T::PriceDecay::get());
+					kept.saturating_pow(elapsed as usize) * price
+				},
+				None => base,
+			};
+
+			price.max(floor).min(ceiling)
+		}
+
+		/// Funds collected in the pallet account.
+		pub fn pot_balance() -> BalanceOf<T> {
+			T::Currency::free_balance(&Self::get_account_id())
+		}
+
+		/// Split `BreederShare` of the pot evenly between the breeds of the period.
+		fn distribute_to_breeders() -> Weight {
+			let breeders = PeriodBreeders::<T>::take();
+			let breeds = breeders.len() as u32;
+			let pot = Self::get_account_id();
+			let available = Self::pot_balance().saturating_sub(T::Currency::minimum_balance());
+			let reward = (T::BreederShare::get() * available) / BalanceOf::<T>::from(breeds.max(1));
+
+			if breeds > 0 && !reward.is_zero() {
+				for breeder in breeders.iter() {
+					let _ = T::Currency::transfer(&pot, breeder, reward, ExistenceRequirement::KeepAlive);
+				}
+				Self::deposit_event(Event::BreederRewardsPaid { breeds, reward });
+			}
+
+			T::DbWeight::get().reads_writes(2 + breeds as u64, 1 + breeds as u64)
+		}
+
+		/// Pay `price` to `seller` with `pay`, splitting off the creator and pot royalties.
+		///
+		/// Kitties minted before creators were recorded have no creator, and royalties too small
+		/// to recreate a reaped creator or an unfunded pot cannot be paid; those shares go to the
+		/// seller.
+		fn settle(
+			kitty_id: KittyId,
+			seller: &T::AccountId,
+			price: BalanceOf<T>,
+			pay: impl Fn(&T::AccountId, BalanceOf<T>) -> DispatchResult,
+		) -> DispatchResult {
+			let pot = Self::get_account_id();
+			let creator = KittyCreators::<T>::get(kitty_id);
+			let creator_amount = match &creator {
+				Some(creator) => Self::payable(creator, T::CreatorRoyalty::get() * price),
+				None => Zero::zero(),
+			};
+			let pot_amount = Self::payable(&pot, T::PotRoyalty::get() * price);
+			let seller_amount = price.saturating_sub(creator_amount).saturating_sub(pot_amount);
+
+			if let Some(creator) = &creator {
+				pay(creator, creator_amount)?;
+			}
+			pay(&pot, pot_amount)?;
+			pay(seller, seller_amount)?;
+
+			Self::deposit_event(Event::RoyaltyPaid { kitty_id, creator, creator_amount, pot_amount });
+			Ok(())
+		}
+
+		/// Pay `amount` out of the reserve of `from` to `to`. Reserved funds cannot be moved to an
+		/// account that does not exist, so those are released and transferred instead.
+		fn pay_reserved(
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			if T::Currency::total_balance(to).is_zero() {
+				let missing = T::Currency::unreserve(from, amount);
+				ensure!(missing.is_zero(), Error::<T>::ReserveMissing);
+				T::Currency::transfer(from, to, amount, ExistenceRequirement::AllowDeath)
+			} else {
+				let missing =
+					T::Currency::repatriate_reserved(from, to, amount, BalanceStatus::Free)?;
+				ensure!(missing.is_zero(), Error::<T>::ReserveMissing);
+				Ok(())
+			}
+		}
+
+		/// `amount` if it can be paid to `who`, zero if `who` does not exist and `amount` is below
+		/// the existential deposit.
+		fn payable(who: &T::AccountId, amount: BalanceOf<T>) -> BalanceOf<T> {
+			if amount < T::Currency::minimum_balance() && T::Currency::total_balance(who).is_zero() {
+				Zero::zero()
+			} else {
+				amount
+			}
+		}
+
+		/// Move `amount` shares of a fractionalized kitty from `from` to `to`.
+		fn transfer_shares_between(
+			kitty_id: KittyId,
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: Shares,
+		) -> DispatchResult {
+			KittyShares::<T>::try_mutate_exists(kitty_id, from, |balance| -> DispatchResult {
+				let remaining = balance
+					.unwrap_or_default()
+					.checked_sub(amount)
+					.ok_or(Error::<T>::InsufficientShares)?;
+				*balance = (!remaining.is_zero()).then_some(remaining);
+				Ok(())
+			})?;
+			KittyShares::<T>::mutate(kitty_id, to, |balance| *balance = balance.saturating_add(amount));
+			Ok(())
+		}
+
+		/// Base URI of the collection of a kitty followed by its item id, like the `tokenURI` of
+		/// ERC-721. `None` if the kitty does not exist or its collection has no base URI.
+		pub fn token_uri(kitty_id: KittyId) -> Option<Vec<u8>> {
+			if !Kitties::<T>::contains_key(kitty_id) {
+				return None
+			}
+			let mut uri = Self::base_uri(kitty_id.0)?.into_inner();
+			let start = uri.len();
+			let mut item_id = kitty_id.1;
+			loop {
+				uri.insert(start, b'0' + (item_id % 10) as u8);
+				item_id /= 10;
+				if item_id == 0 {
+					break
+				}
+			}
+			Some(uri)
+		}
+
+		/// All attributes of a kitty, as `(namespace, key, value)`.
+		pub fn kitty_attributes(kitty_id: KittyId) -> Vec<(AttributeNamespace, Vec<u8>, Vec<u8>)> {
+			Attributes::<T>::iter_prefix(kitty_id)
+				.map(|((namespace, key), value)| (namespace, key.into_inner(), value.into_inner()))
+				.collect()
+		}
+
+		/// Whether a kitty may change owner, i.e. it is not soulbound.
+		pub fn is_transferable(kitty_id: KittyId) -> bool {
+			!NonTransferable::<T>::contains_key(kitty_id)
+		}
+
+		/// Whether `who` holds the usage rights of a kitty: the lessee while it is leased out, the
+		/// owner otherwise.
+		pub fn can_use(kitty_id: KittyId, who: &T::AccountId) -> bool {
+			match Leases::<T>::get(kitty_id) {
+				Some(lease) => lease.lessee == *who,
+				None => Self::kitty_owner(kitty_id).as_ref() == Some(who),
+			}
+		}
+
+		/// Return the usage rights of the kitties whose lease ends in block `now` to their owners.
+		fn end_leases(now: T::BlockNumber) -> Weight {
+			let kitties = LeaseExpiries::<T>::take(now);
+			for kitty_id in kitties.iter() {
+				if let Some(lease) = Leases::<T>::take(kitty_id) {
+					// The lessee no longer competes with the kitty.
+					Entrants::<T>::mutate(|entrants| {
+						entrants.retain(|entrant| *entrant != (*kitty_id, lease.lessee.clone()))
+					});
+					T::OnKittyEvent::on_lease_ended(&lease.lessee, *kitty_id);
+					Self::deposit_event(Event::LeaseEnded { kitty_id: *kitty_id, lessee: lease.lessee });
+				}
+			}
+
+			let count = kitties.len() as u64;
+			T::DbWeight::get().reads_writes(1 + count, 1 + count)
+		}
+
+		/// Ancestor tree of `kitty_id` up to `generations` generations back, if the kitty exists.
+		/// `generations` is capped at `MaxPedigreeDepth`.
+		pub fn lineage(kitty_id: KittyId, generations: u32) -> Option<Pedigree> {
+			let generations = generations.min(T::MaxPedigreeDepth::get());
+			Kitties::<T>::contains_key(kitty_id).then(|| Self::pedigree(kitty_id, generations))
+		}
+
+		fn pedigree(kitty_id: KittyId, generations: u32) -> Pedigree {
+			let parents = match KittyParents::<T>::get(kitty_id) {
+				Some((parent_1, parent_2)) if generations > 0 => sp_std::vec![
+					Self::pedigree(parent_1, generations - 1),
+					Self::pedigree(parent_2, generations - 1),
+				],
+				_ => Vec::new(),
+			};
+			Pedigree { kitty_id, parents }
+		}
+
+		/// Whether the two kitties share an ancestor within `InbreedingGenerations`. Each kitty
+		/// counts as its own ancestor, so breeding with a parent or grandparent is caught too.
+		fn is_inbreeding(kitty_id_1: KittyId, kitty_id_2: KittyId) -> bool {
+			let generations = T::InbreedingGenerations::get();
+			if generations.is_zero() {
+				return false
+			}
+
+			let ancestors = Self::ancestors(kitty_id_1, generations);
+			!ancestors.is_disjoint(&Self::ancestors(kitty_id_2, generations))
+		}
+
+		fn ancestors(kitty_id: KittyId, generations: u32) -> BTreeSet<KittyId> {
+			let mut ancestors = BTreeSet::new();
+			let mut current = sp_std::vec![kitty_id];
+			for _ in 0..generations {
+				ancestors.extend(current.iter().copied());
+				current = current
+					.iter()
+					.filter_map(|kitty_id| KittyParents::<T>::get(kitty_id))
+					.flat_map(|(parent_1, parent_2)| [parent_1, parent_2])
+					.filter(|parent| !ancestors.contains(parent))
+					.collect();
+			}
+			ancestors.extend(current);
+			ancestors
+		}
+
+		/// Give a kitty to `new_owner`. Listings of the previous owner do not carry over: the kitty
+		/// is taken off sale, its lease terms and competition entry are dropped and standing offers
+		/// are released.
+		fn set_owner(kitty_id: KittyId, new_owner: &T::AccountId) {
+			let previous =
+				KittyOwner::<T>::mutate(kitty_id, |owner| owner.replace(new_owner.clone()));
+			KittyOnSale::<T>::remove(kitty_id);
+			LeaseOffers::<T>::remove(kitty_id);
+			Entrants::<T>::mutate(|entrants| entrants.retain(|(entered, _)| *entered != kitty_id));
+
+			let bidders = Offers::<T>::iter_key_prefix(kitty_id).collect::<Vec<_>>();
+			for who in bidders {
+				if let Some(amount) = Self::remove_offer(kitty_id, &who) {
+					Self::deposit_event(Event::OfferWithdrawn { who, kitty_id, amount });
+				}
+			}
+			OfferCount::<T>::remove(kitty_id);
+
+			if let Some(owner) = previous {
+				T::OnKittyEvent::on_transferred(&owner, new_owner, kitty_id);
+			}
+		}
+
+		/// Remove an offer and release its reserve, returning the amount that was offered.
+		fn remove_offer(kitty_id: KittyId, who: &T::AccountId) -> Option<BalanceOf<T>> {
+			let offer = Offers::<T>::take(kitty_id, who)?;
+			OfferCount::<T>::mutate(kitty_id, |count| *count = count.saturating_sub(1));
+			T::Currency::unreserve(who, offer.amount);
+			T::OnKittyEvent::on_offer_removed(who, kitty_id, offer.amount);
+			Some(offer.amount)
+		}
+
+		/// Remove offers that expired before block `now`, for as long as `remaining_weight` allows.
+		///
+		/// Each call continues the scan of `Offers` where the previous one stopped, and starts over
+		/// once the end of the map is reached.
+		fn clean_expired_offers(now: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let db = T::DbWeight::get();
+			let per_offer = db.reads_writes(1, 3);
+			let mut used = db.reads_writes(1, 1);
+			if !remaining_weight.all_gte(used.saturating_add(per_offer)) {
+				return Weight::zero()
+			}
+
+			let mut offers = match OfferCleanupCursor::<T>::take() {
+				Some(cursor) => Offers::<T>::iter_from(cursor.into_inner()),
+				None => Offers::<T>::iter(),
+			};
+			let mut expired = Vec::new();
+			let mut finished = false;
+			while remaining_weight.all_gte(used.saturating_add(per_offer)) {
+				let (kitty_id, who, offer) = match offers.next() {
+					Some(offer) => offer,
+					None => {
+						finished = true;
+						break
+					},
+				};
+				used = used.saturating_add(per_offer);
+				if offer.expiry < now {
+					expired.push((kitty_id, who));
+				}
+			}
+
+			if !finished {
+				if let Ok(cursor) = BoundedVec::try_from(offers.last_raw_key().to_vec()) {
+					OfferCleanupCursor::<T>::put(cursor);
+				}
+			}
+
+			for (kitty_id, who) in expired {
+				if let Some(amount) = Self::remove_offer(kitty_id, &who) {
+					Self::deposit_event(Event::OfferExpired { who, kitty_id, amount });
+				}
+			}
+
+			used
+		}
+
+		fn raise_price(collection_id: CollectionId, paid: BalanceOf<T>) {
+			let price = paid.saturating_add(T::PriceIncrease::get() * paid);
+			MintPrice::<T>::insert(collection_id, (price, <frame_system::Pallet<T>>::block_number()));
+		}
+
+		/// Move the mint price of a collection `SalePriceWeight` of the way to the price one of its
+		/// kitties was just sold for.
+		fn record_sale(collection_id: CollectionId, sold_for: BalanceOf<T>) {
+			let current = Self::current_price(collection_id);
+			let weight = T::SalePriceWeight::get();
+			let price = if sold_for > current {
+				current.saturating_add(weight * (sold_for - current))
+			} else {
+				current.saturating_sub(weight * (current - sold_for))
+			};
+			MintPrice::<T>::insert(collection_id, (price, <frame_system::Pallet<T>>::block_number()));
+		}
 	}
 }
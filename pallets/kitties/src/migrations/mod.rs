@@ -1,11 +1,22 @@
-pub use v2 as current_version; // 当前版本
+pub use v3 as current_version; // 当前版本
 pub mod v2;
+pub mod v3;
 
-use crate::{Config, Kitties, Pallet};
+use crate::{
+	BalanceOf, Config, Entrants, Kitties, KittyChildren, KittyCreators, KittyOnSale, KittyOwner,
+	KittyParents, KittyRatings, KittyShares, LeaseExpiries, LeaseOffers, Leases, MintPrice,
+	NextItemId, OfferCleanupCursor, OfferCount, Offers, Pallet, PriceBounds, Shares, TotalShares,
+	TotalSupply,
+};
+use current_version::{Kitty, Lease, LeaseTerms, Offer, DEFAULT_COLLECTION};
 use frame_support::{
-	migration::storage_key_iter, pallet_prelude::*, traits::GetStorageVersion, weights::Weight,
-	StoragePrefixedMap,
+	migration::{put_storage_value, storage_iter, storage_key_iter, take_storage_value},
+	pallet_prelude::*,
+	traits::GetStorageVersion,
+	weights::Weight,
+	ReversibleStorageHasher, StorageHasher, StoragePrefixedMap,
 };
+use sp_std::vec::Vec;
 mod v0;
 mod v1;
 
@@ -13,17 +24,156 @@ pub(crate) fn upgrade_storage<T: Config>() -> Weight {
 	let on_chain_ver: StorageVersion = Pallet::<T>::on_chain_storage_version();
 	if on_chain_ver == v1::STORAGE_VERSION {
 		from_v1::<T>();
-		current_version::STORAGE_VERSION.put::<Pallet::<T>>();
 	} else if on_chain_ver == v0::STORAGE_VERSION {
 		from_v0::<T>();
-		current_version::STORAGE_VERSION.put::<Pallet::<T>>();
+	}
+
+	if on_chain_ver < current_version::STORAGE_VERSION {
+		let count = from_v2::<T>();
+		current_version::STORAGE_VERSION.put::<Pallet<T>>();
+		return T::DbWeight::get().reads_writes(count + 1, count + 1)
 	}
 
 	Weight::zero()
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
-/// v1 -> current
+/// v2 -> current
+
+/// Every existing kitty becomes item `kitty_id` of `DEFAULT_COLLECTION`, so all maps keyed by,
+/// or holding, kitty ids are rewritten. Returns the number of entries moved.
+fn from_v2<T: Config>() -> u64 {
+	let id = |kitty_id: v2::KittyId| (DEFAULT_COLLECTION, kitty_id);
+	let ids = |kitty_ids: Vec<v2::KittyId>| kitty_ids.into_iter().map(id).collect::<Vec<_>>();
+	let mut count = 0;
+
+	let kitties = take_v2_map::<T, Kitty>(Kitties::<T>::storage_prefix());
+	TotalSupply::<T>::put(kitties.len() as u32);
+	for (kitty_id, kitty) in kitties {
+		Kitties::<T>::insert(id(kitty_id), kitty);
+		count += 1;
+	}
+	for (kitty_id, ()) in take_v2_map::<T, ()>(KittyOnSale::<T>::storage_prefix()) {
+		KittyOnSale::<T>::insert(id(kitty_id), ());
+		count += 1;
+	}
+	for (kitty_id, (parent_1, parent_2)) in
+		take_v2_map::<T, (v2::KittyId, v2::KittyId)>(KittyParents::<T>::storage_prefix())
+	{
+		KittyParents::<T>::insert(id(kitty_id), (id(parent_1), id(parent_2)));
+		count += 1;
+	}
+	for (kitty_id, children) in
+		take_v2_map::<T, Vec<v2::KittyId>>(KittyChildren::<T>::storage_prefix())
+	{
+		KittyChildren::<T>::insert(id(kitty_id), BoundedVec::truncate_from(ids(children)));
+		count += 1;
+	}
+	for (kitty_id, owner) in take_v2_map::<T, T::AccountId>(KittyOwner::<T>::storage_prefix()) {
+		KittyOwner::<T>::insert(id(kitty_id), owner);
+		count += 1;
+	}
+	for (kitty_id, creator) in take_v2_map::<T, T::AccountId>(KittyCreators::<T>::storage_prefix())
+	{
+		KittyCreators::<T>::insert(id(kitty_id), creator);
+		count += 1;
+	}
+	for (kitty_id, who, offer) in
+		take_v2_double_map::<T, Offer<BalanceOf<T>, T::BlockNumber>>(Offers::<T>::storage_prefix())
+	{
+		Offers::<T>::insert(id(kitty_id), who, offer);
+		count += 1;
+	}
+	for (kitty_id, offers) in take_v2_map::<T, u32>(OfferCount::<T>::storage_prefix()) {
+		OfferCount::<T>::insert(id(kitty_id), offers);
+		count += 1;
+	}
+	OfferCleanupCursor::<T>::kill();
+	for (kitty_id, terms) in take_v2_map::<T, LeaseTerms<T::AccountId, BalanceOf<T>, T::BlockNumber>>(
+		LeaseOffers::<T>::storage_prefix(),
+	) {
+		LeaseOffers::<T>::insert(id(kitty_id), terms);
+		count += 1;
+	}
+	for (kitty_id, lease) in
+		take_v2_map::<T, Lease<T::AccountId, T::BlockNumber>>(Leases::<T>::storage_prefix())
+	{
+		Leases::<T>::insert(id(kitty_id), lease);
+		count += 1;
+	}
+	LeaseExpiries::<T>::translate_values::<Vec<v2::KittyId>, _>(|kitties| {
+		count += 1;
+		Some(BoundedVec::truncate_from(ids(kitties)))
+	});
+	for (kitty_id, shares) in take_v2_map::<T, Shares>(TotalShares::<T>::storage_prefix()) {
+		TotalShares::<T>::insert(id(kitty_id), shares);
+		count += 1;
+	}
+	for (kitty_id, who, shares) in
+		take_v2_double_map::<T, Shares>(KittyShares::<T>::storage_prefix())
+	{
+		KittyShares::<T>::insert(id(kitty_id), who, shares);
+		count += 1;
+	}
+	let _ = Entrants::<T>::translate::<Vec<(v2::KittyId, T::AccountId)>, _>(|entrants| {
+		let entrants = entrants?.into_iter().map(|(kitty_id, who)| (id(kitty_id), who));
+		Some(BoundedVec::truncate_from(entrants.collect()))
+	});
+	for (kitty_id, rating) in take_v2_map::<T, u32>(KittyRatings::<T>::storage_prefix()) {
+		KittyRatings::<T>::insert(id(kitty_id), rating);
+		count += 1;
+	}
+
+	let module = Kitties::<T>::module_prefix();
+	if let Some(next_kitty_id) = take_storage_value::<v2::KittyId>(module, b"NextKittyId", &[]) {
+		NextItemId::<T>::insert(DEFAULT_COLLECTION, next_kitty_id);
+	}
+	if let Some(price) =
+		take_storage_value::<(BalanceOf<T>, T::BlockNumber)>(module, b"MintPrice", &[])
+	{
+		MintPrice::<T>::insert(DEFAULT_COLLECTION, price);
+	}
+	if let Some(bounds) =
+		take_storage_value::<(BalanceOf<T>, BalanceOf<T>)>(module, b"PriceBounds", &[])
+	{
+		PriceBounds::<T>::insert(DEFAULT_COLLECTION, bounds);
+	}
+
+	count + 5
+}
+
+/// Remove all entries of a v2 map keyed by kitty id and return them.
+fn take_v2_map<T: Config, V: Decode>(item: &[u8]) -> Vec<(v2::KittyId, V)> {
+	let module = Kitties::<T>::module_prefix();
+	storage_key_iter::<v2::KittyId, V, Blake2_128Concat>(module, item)
+		.drain()
+		.collect()
+}
+
+/// Remove all entries of a v2 double map keyed by kitty id and account, and return them.
+fn take_v2_double_map<T: Config, V: Decode>(item: &[u8]) -> Vec<(v2::KittyId, T::AccountId, V)> {
+	let module = Kitties::<T>::module_prefix();
+	storage_iter::<V>(module, item)
+		.drain()
+		.filter_map(|(key, value)| {
+			let mut key = Blake2_128Concat::reverse(&key);
+			let kitty_id = v2::KittyId::decode(&mut key).ok()?;
+			let mut key = Blake2_128Concat::reverse(key);
+			let who = T::AccountId::decode(&mut key).ok()?;
+			Some((kitty_id, who, value))
+		})
+		.collect()
+}
+
+/// Write a kitty in the v2 layout, which `from_v2` then moves to the default collection.
+fn put_v2_kitty<T: Config>(kitty_id: v2::KittyId, kitty: &v2::Kitty) {
+	let module = Kitties::<T>::module_prefix();
+	let item = Kitties::<T>::storage_prefix();
+	put_storage_value(module, item, &Blake2_128Concat::hash(&kitty_id.encode()), kitty);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+/// v1 -> v2
 
 fn from_v1<T: Config>() {
 	let module = Kitties::<T>::module_prefix();
@@ -32,12 +182,12 @@ fn from_v1<T: Config>() {
 	for (kitty_id, kitty_old) in
 		storage_key_iter::<v1::KittyId, v1::Kitty, Blake2_128Concat>(module, item).drain()
 	{
-		let kitty = current_version::Kitty { name: from_name_v1(&kitty_old.name, b"5678"), dna: kitty_old.dna };
-		Kitties::<T>::insert(kitty_id, &kitty);
+		let kitty = v2::Kitty { name: from_name_v1(&kitty_old.name, b"5678"), dna: kitty_old.dna };
+		put_v2_kitty::<T>(kitty_id, &kitty);
 	}
 }
 
-fn from_name_v1(name_v1: &v1::KittyName, append: &[u8; 4]) -> current_version::KittyName {
+fn from_name_v1(name_v1: &v1::KittyName, append: &[u8; 4]) -> v2::KittyName {
 	let mut result = [0; 8];
 	result[..4].copy_from_slice(name_v1);
 	result[4..].copy_from_slice(append);
@@ -45,7 +195,7 @@ fn from_name_v1(name_v1: &v1::KittyName, append: &[u8; 4]) -> current_version::K
 }
 
 ////////////////////////////////////////////////////////////////////////////////////////////////////
-/// v0 -> current
+/// v0 -> v2
 
 fn from_v0<T: Config>() {
 	let module = Kitties::<T>::module_prefix();
@@ -54,7 +204,7 @@ fn from_v0<T: Config>() {
 	for (kitty_id, kitty_old) in
 		storage_key_iter::<v0::KittyId, v0::Kitty, Blake2_128Concat>(module, item).drain()
 	{
-		let kitty = current_version::Kitty { name: *b"12345678", dna: kitty_old.0 };
-		Kitties::<T>::insert(kitty_id, &kitty);
+		let kitty = v2::Kitty { name: *b"12345678", dna: kitty_old.0 };
+		put_v2_kitty::<T>(kitty_id, &kitty);
 	}
-}
\ No newline at end of file
+}
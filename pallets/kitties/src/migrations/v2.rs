@@ -13,4 +13,31 @@ pub type KittyName = [u8; 8];
 pub struct Kitty {
 	pub name: KittyName,
 	pub dna: KittyDna,
-}
\ No newline at end of file
+}
+/// Standing offer on a kitty. `amount` is reserved from the bidder until the offer is accepted,
+/// withdrawn or cleaned up after `expiry`.
+#[derive(Clone, PartialEq, Eq, TypeInfo, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+pub struct Offer<Balance, BlockNumber> {
+	pub amount: Balance,
+	/// Last block in which the offer can be accepted.
+	pub expiry: BlockNumber,
+}
+
+/// Lease proposed by the owner of a kitty, waiting for the lessee to accept it.
+#[derive(Clone, PartialEq, Eq, TypeInfo, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+pub struct LeaseTerms<AccountId, Balance, BlockNumber> {
+	/// Owner that proposed the lease. The terms lapse if the kitty changes hands.
+	pub lessor: AccountId,
+	pub lessee: AccountId,
+	/// Number of blocks the lease lasts once accepted.
+	pub duration: BlockNumber,
+	/// Paid by the lessee to the owner when accepting.
+	pub fee: Balance,
+}
+
+/// Running lease. The lessee holds the usage rights of the kitty until `expires_at`.
+#[derive(Clone, PartialEq, Eq, TypeInfo, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+pub struct Lease<AccountId, BlockNumber> {
+	pub lessee: AccountId,
+	pub expires_at: BlockNumber,
+}
@@ -0,0 +1,68 @@
+use super::v2;
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{pallet_prelude::*, traits::StorageVersion};
+use scale_info::TypeInfo;
+
+pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(3);
+
+pub type CollectionId = u32;
+pub type ItemId = u32;
+/// Collection of the kitty and its position in the collection.
+pub type KittyId = (CollectionId, ItemId);
+pub type KittyDna = v2::KittyDna;
+pub type KittyName = v2::KittyName;
+
+pub use v2::{Kitty, Lease, LeaseTerms, Offer};
+
+/// Collection every kitty minted before collections existed belongs to. It is available without
+/// being created, with `KittyPrice` and no limits, until root configures it.
+pub const DEFAULT_COLLECTION: CollectionId = 0;
+
+/// Which kitties the kitties of a collection may breed with.
+#[derive(Clone, Copy, PartialEq, Eq, TypeInfo, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+pub enum BreedingRule {
+	/// Kitties of the collection cannot breed.
+	Disabled,
+	/// Only with kitties of the same collection.
+	SameCollection,
+	/// With any kitty whose own collection allows it.
+	Open,
+}
+
+/// Settings of a collection, chosen by the account that created it.
+#[derive(Clone, PartialEq, Eq, TypeInfo, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+pub struct CollectionConfig<Balance, BlockNumber> {
+	/// Starting mint price, and the floor until one is set with `set_price_bounds`.
+	pub mint_price: Balance,
+	/// Maximum number of kitties created or bred into the collection.
+	pub max_supply: Option<u32>,
+	/// First and last block in which kitties can be created or bred into the collection.
+	pub mint_window: Option<(BlockNumber, BlockNumber)>,
+	pub breeding: BreedingRule,
+}
+
+#[derive(Clone, PartialEq, Eq, TypeInfo, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+pub struct CollectionInfo<AccountId, Balance, BlockNumber> {
+	/// Collection admin that created the collection, `None` if root did.
+	pub admin: Option<AccountId>,
+	pub config: CollectionConfig<Balance, BlockNumber>,
+}
+
+pub type SwapId = u32;
+
+/// Trade proposed with `propose_swap`, until the counterparty accepts it or it is cancelled.
+/// `top_up` is reserved from the proposer in the meantime.
+#[derive(Clone, PartialEq, Eq, TypeInfo, Encode, Decode, MaxEncodedLen, RuntimeDebug)]
+#[scale_info(skip_type_params(MaxKitties))]
+pub struct Swap<AccountId, Balance, BlockNumber, MaxKitties: Get<u32>> {
+	pub proposer: AccountId,
+	pub counterparty: AccountId,
+	/// Kitties of the proposer going to the counterparty.
+	pub offered: BoundedVec<KittyId, MaxKitties>,
+	/// Kitties of the counterparty going to the proposer.
+	pub wanted: BoundedVec<KittyId, MaxKitties>,
+	/// Paid by the proposer to the counterparty on top of the kitties.
+	pub top_up: Balance,
+	/// Last block in which the swap can be accepted.
+	pub expiry: BlockNumber,
+}
@@ -98,11 +98,13 @@ use sp_core::H256;
 use sp_runtime::{
 	testing::Header,
 	traits::{BlakeTwo256, IdentityLookup},
+	Permill,
 };
 use frame_support::{PalletId, parameter_types};
 use pallet_balances;
 
 use pallet_insecure_randomness_collective_flip;
+use std::cell::RefCell;
 
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
@@ -174,6 +176,12 @@ impl pallet_balances::Config for Test {
 parameter_types! {
 	pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
 	pub KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub KittyPriceIncrease: Permill = Permill::from_percent(10);
+	pub KittyPriceDecay: Permill = Permill::from_percent(1);
+	pub KittySalePriceWeight: Permill = Permill::from_percent(50);
+	pub KittyBreederShare: Permill = Permill::from_percent(50);
+	pub KittyCreatorRoyalty: Permill = Permill::from_percent(10);
+	pub KittyPotRoyalty: Permill = Permill::from_percent(5);
 }
 
 impl pallet_kitties::Config for Test {
@@ -182,6 +190,210 @@ impl pallet_kitties::Config for Test {
 	type Currency = Balances;
 	type KittyPrice = KittyPrice;
 	type PalletId = KittyPalletId;
+	type PriceIncrease = KittyPriceIncrease;
+	type PriceDecay = KittyPriceDecay;
+	type SalePriceWeight = KittySalePriceWeight;
+	type SpendOrigin = system::EnsureRoot<u64>;
+	type DistributionPeriod = ConstU64<10>;
+	type BreederShare = KittyBreederShare;
+	type MaxRewardedBreeders = ConstU32<2>;
+	type CreatorRoyalty = KittyCreatorRoyalty;
+	type PotRoyalty = KittyPotRoyalty;
+	type MaxOffersPerKitty = ConstU32<2>;
+	type MinOffer = ConstU128<1_000>;
+	type MaxOfferDuration = ConstU64<100>;
+	type MaxChildren = ConstU32<3>;
+	type InbreedingGenerations = ConstU32<2>;
+	type MaxPedigreeDepth = ConstU32<2>;
+	type MaxLeasesPerBlock = ConstU32<1>;
+	type EntryFee = KittyPrice;
+	type CompetitionPeriod = ConstU64<5>;
+	type MaxEntrants = ConstU32<2>;
+	type MaxSupply = ConstU32<100>;
+	type MintLimitPerPeriod = ConstU32<25>;
+	type MintPeriod = ConstU64<10>;
+	type MintOrigin = system::EnsureRoot<u64>;
+	type SoulboundOrigin = system::EnsureRoot<u64>;
+	type KeyLimit = ConstU32<8>;
+	type ValueLimit = ConstU32<16>;
+	type MaxAttributes = ConstU32<2>;
+	type UriLimit = ConstU32<32>;
+	type MaxBatchSize = ConstU32<3>;
+	type MaxSwapKitties = ConstU32<2>;
+	type OnKittyEvent = (RecordKittyEvents, ());
+}
+
+/// Lifecycle notification received by `RecordKittyEvents`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum KittyEvent {
+	Created(u64, pallet_kitties::KittyId),
+	Bred(u64, pallet_kitties::KittyId),
+	Transferred(u64, u64, pallet_kitties::KittyId),
+	Sold(u64, u64, pallet_kitties::KittyId, Balance),
+	Swapped(u64, u64, Vec<pallet_kitties::KittyId>, Vec<pallet_kitties::KittyId>, Balance),
+	Listed(u64, pallet_kitties::KittyId),
+	OfferMade(u64, pallet_kitties::KittyId, Balance),
+	OfferRemoved(u64, pallet_kitties::KittyId, Balance),
+	LeaseOffered(u64, u64, pallet_kitties::KittyId),
+	Leased(u64, u64, pallet_kitties::KittyId),
+	LeaseEnded(u64, pallet_kitties::KittyId),
+	Fractionalized(u64, pallet_kitties::KittyId, pallet_kitties::Shares),
+	Redeemed(u64, pallet_kitties::KittyId),
+	SwapProposed(u64, u64, pallet_kitties::SwapId),
+	SwapCancelled(u64, u64, pallet_kitties::SwapId),
+	SharesTransferred(u64, u64, pallet_kitties::KittyId, pallet_kitties::Shares),
+	Entered(u64, pallet_kitties::KittyId),
+	CompetitionWon(u64, pallet_kitties::KittyId, Balance, u32),
+	AttributeSet(pallet_kitties::KittyId, pallet_kitties::AttributeNamespace, Vec<u8>),
+	AttributeCleared(pallet_kitties::KittyId, pallet_kitties::AttributeNamespace, Vec<u8>),
+	CollectionCreated(pallet_kitties::CollectionId, Option<u64>),
+	CollectionUpdated(pallet_kitties::CollectionId),
+}
+
+thread_local! {
+	static KITTY_EVENTS: RefCell<Vec<KittyEvent>> = RefCell::new(Vec::new());
+}
+
+/// Records the lifecycle notifications of the pallet, see `kitty_events`.
+pub struct RecordKittyEvents;
+
+impl pallet_kitties::OnKittyEvent<u64, Balance> for RecordKittyEvents {
+	fn on_created(owner: &u64, kitty_id: pallet_kitties::KittyId) {
+		record(KittyEvent::Created(*owner, kitty_id));
+	}
+
+	fn on_bred(
+		owner: &u64,
+		kitty_id: pallet_kitties::KittyId,
+		_parents: (pallet_kitties::KittyId, pallet_kitties::KittyId),
+	) {
+		record(KittyEvent::Bred(*owner, kitty_id));
+	}
+
+	fn on_transferred(from: &u64, to: &u64, kitty_id: pallet_kitties::KittyId) {
+		// Handlers see the new owner.
+		assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(*to));
+		record(KittyEvent::Transferred(*from, *to, kitty_id));
+	}
+
+	fn on_sold(seller: &u64, buyer: &u64, kitty_id: pallet_kitties::KittyId, price: Balance) {
+		record(KittyEvent::Sold(*seller, *buyer, kitty_id, price));
+	}
+
+	fn on_swapped(
+		proposer: &u64,
+		counterparty: &u64,
+		offered: &[pallet_kitties::KittyId],
+		wanted: &[pallet_kitties::KittyId],
+		top_up: Balance,
+	) {
+		record(KittyEvent::Swapped(
+			*proposer,
+			*counterparty,
+			offered.to_vec(),
+			wanted.to_vec(),
+			top_up,
+		));
+	}
+
+	fn on_listed(owner: &u64, kitty_id: pallet_kitties::KittyId) {
+		record(KittyEvent::Listed(*owner, kitty_id));
+	}
+
+	fn on_offer_made(bidder: &u64, kitty_id: pallet_kitties::KittyId, amount: Balance) {
+		record(KittyEvent::OfferMade(*bidder, kitty_id, amount));
+	}
+
+	fn on_offer_removed(bidder: &u64, kitty_id: pallet_kitties::KittyId, amount: Balance) {
+		record(KittyEvent::OfferRemoved(*bidder, kitty_id, amount));
+	}
+
+	fn on_lease_offered(lessor: &u64, lessee: &u64, kitty_id: pallet_kitties::KittyId) {
+		record(KittyEvent::LeaseOffered(*lessor, *lessee, kitty_id));
+	}
+
+	fn on_leased(lessor: &u64, lessee: &u64, kitty_id: pallet_kitties::KittyId) {
+		record(KittyEvent::Leased(*lessor, *lessee, kitty_id));
+	}
+
+	fn on_lease_ended(lessee: &u64, kitty_id: pallet_kitties::KittyId) {
+		record(KittyEvent::LeaseEnded(*lessee, kitty_id));
+	}
+
+	fn on_fractionalized(
+		owner: &u64,
+		kitty_id: pallet_kitties::KittyId,
+		shares: pallet_kitties::Shares,
+	) {
+		record(KittyEvent::Fractionalized(*owner, kitty_id, shares));
+	}
+
+	fn on_redeemed(owner: &u64, kitty_id: pallet_kitties::KittyId) {
+		record(KittyEvent::Redeemed(*owner, kitty_id));
+	}
+
+	fn on_swap_proposed(proposer: &u64, counterparty: &u64, swap_id: pallet_kitties::SwapId) {
+		record(KittyEvent::SwapProposed(*proposer, *counterparty, swap_id));
+	}
+
+	fn on_swap_cancelled(proposer: &u64, counterparty: &u64, swap_id: pallet_kitties::SwapId) {
+		record(KittyEvent::SwapCancelled(*proposer, *counterparty, swap_id));
+	}
+
+	fn on_shares_transferred(
+		from: &u64,
+		to: &u64,
+		kitty_id: pallet_kitties::KittyId,
+		amount: pallet_kitties::Shares,
+	) {
+		record(KittyEvent::SharesTransferred(*from, *to, kitty_id, amount));
+	}
+
+	fn on_entered(who: &u64, kitty_id: pallet_kitties::KittyId) {
+		record(KittyEvent::Entered(*who, kitty_id));
+	}
+
+	fn on_competition_won(
+		winner: &u64,
+		kitty_id: pallet_kitties::KittyId,
+		prize: Balance,
+		rating: u32,
+	) {
+		record(KittyEvent::CompetitionWon(*winner, kitty_id, prize, rating));
+	}
+
+	fn on_attribute_set(
+		kitty_id: pallet_kitties::KittyId,
+		namespace: pallet_kitties::AttributeNamespace,
+		key: &[u8],
+	) {
+		record(KittyEvent::AttributeSet(kitty_id, namespace, key.to_vec()));
+	}
+
+	fn on_attribute_cleared(
+		kitty_id: pallet_kitties::KittyId,
+		namespace: pallet_kitties::AttributeNamespace,
+		key: &[u8],
+	) {
+		record(KittyEvent::AttributeCleared(kitty_id, namespace, key.to_vec()));
+	}
+
+	fn on_collection_created(collection_id: pallet_kitties::CollectionId, admin: Option<&u64>) {
+		record(KittyEvent::CollectionCreated(collection_id, admin.copied()));
+	}
+
+	fn on_collection_updated(collection_id: pallet_kitties::CollectionId) {
+		record(KittyEvent::CollectionUpdated(collection_id));
+	}
+}
+
+fn record(event: KittyEvent) {
+	KITTY_EVENTS.with(|events| events.borrow_mut().push(event));
+}
+
+/// Notifications recorded by `RecordKittyEvents` since the start of the test.
+pub fn kitty_events() -> Vec<KittyEvent> {
+	KITTY_EVENTS.with(|events| events.borrow().clone())
 }
 
 impl pallet_insecure_randomness_collective_flip::Config for Test {}
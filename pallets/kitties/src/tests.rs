@@ -1,15 +1,17 @@
-use crate::{Error, mock::*, Event};
-use frame_support::{assert_ok, assert_noop};
+use crate::{
+    AttributeNamespace, BreedingRule, CollectionConfig, Error, mock::*, Event, Offer, Pedigree, DEFAULT_COLLECTION,
+};
+use frame_support::{assert_ok, assert_noop, traits::Hooks, weights::Weight};
 
 #[test]
 fn it_works_for_create(){
     new_test_ext().execute_with(|| {
-        let kitty_id = 0;
+        let kitty_id = (DEFAULT_COLLECTION, 0);
         let account_id = 1;
         let name = *b"8--bytes";
 
-        assert_eq!(KittiesModule::next_kitty_id(), kitty_id);
-        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id),name));
+        assert_eq!(KittiesModule::next_item_id(DEFAULT_COLLECTION), 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
         let kitty = KittiesModule::kitties(kitty_id);
         // Asserts that a `KittyCreated` event has been emitted
         System::assert_has_event(Event::KittyCreated {
@@ -17,14 +19,14 @@ fn it_works_for_create(){
             kitty_id,
             kitty: kitty.unwrap(),
         }.into());
-        assert_eq!(KittiesModule::next_kitty_id(), kitty_id + 1);
+        assert_eq!(KittiesModule::next_item_id(DEFAULT_COLLECTION), 1);
         assert_eq!(KittiesModule::kitties(kitty_id).is_some(),true);
         assert_eq!(KittiesModule::kitty_owner(kitty_id),Some(account_id));
         assert_eq!(KittiesModule::kitty_parents(kitty_id), None);
 
-        crate::NextKittyId::<Test>::set(crate::KittyId::max_value());
+        crate::NextItemId::<Test>::insert(DEFAULT_COLLECTION, crate::ItemId::max_value());
         assert_noop!(
-            KittiesModule::create(RuntimeOrigin::signed(account_id),name),
+            KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name),
             Error::<Test>::InvalidKittyId
         );
     });
@@ -33,7 +35,7 @@ fn it_works_for_create(){
 #[test]
 fn it_works_for_breed() {
     new_test_ext().execute_with(|| {
-        let kitty_id = 0;
+        let kitty_id = (DEFAULT_COLLECTION, 0);
         let account_id = 1;
         let name = *b"8--bytes";
 
@@ -43,39 +45,47 @@ fn it_works_for_breed() {
         );
 
         assert_noop!(
-            KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, name),
+            KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, (DEFAULT_COLLECTION, 1), name),
             Error::<Test>::InvalidKittyId
         );
 
-        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name));
-        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
 
-        assert_eq!(KittiesModule::next_kitty_id(), kitty_id + 2);
-        assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, kitty_id + 1, name));
+        assert_eq!(KittiesModule::next_item_id(DEFAULT_COLLECTION), 2);
+        assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), kitty_id, (DEFAULT_COLLECTION, 1), name));
         // Asserts that a `KittyBred` event has been emitted
         System::assert_has_event(Event::KittyBred {
             who: account_id,
-            kitty_id: kitty_id + 2,
-            kitty: KittiesModule::kitties(kitty_id + 2).unwrap(),
+            kitty_id: (DEFAULT_COLLECTION, 2),
+            kitty: KittiesModule::kitties((DEFAULT_COLLECTION, 2)).unwrap(),
         }.into());
 
-        let breed_kitty_id = 2;
-        assert_eq!(KittiesModule::next_kitty_id(),breed_kitty_id + 1);
+        let breed_kitty_id = (DEFAULT_COLLECTION, 2);
+        assert_eq!(KittiesModule::next_item_id(DEFAULT_COLLECTION), 3);
         assert_eq!(KittiesModule::kitties(breed_kitty_id).is_some(), true);
         assert_eq!(KittiesModule::kitty_owner(breed_kitty_id), Some(account_id));
-        assert_eq!(KittiesModule::kitty_parents(breed_kitty_id), Some((kitty_id, kitty_id + 1)));
+        assert_eq!(KittiesModule::kitty_parents(breed_kitty_id), Some((kitty_id, (DEFAULT_COLLECTION, 1))));
+
+        // Every gene of the child comes from one of its parents.
+        let parents = [KittiesModule::kitties(kitty_id).unwrap(), KittiesModule::kitties((DEFAULT_COLLECTION, 1)).unwrap()];
+        let child = KittiesModule::kitties(breed_kitty_id).unwrap();
+        assert_ne!(child.dna, [0u8; 16]);
+        for ((gene, gene_1), gene_2) in child.dna.iter().zip(parents[0].dna).zip(parents[1].dna) {
+            assert_eq!(gene & !(gene_1 | gene_2), 0);
+        }
     });
 }
 
 #[test]
 fn it_works_for_transfer() {
     new_test_ext().execute_with(|| {
-        let kitty_id = 0;
+        let kitty_id = (DEFAULT_COLLECTION, 0);
         let account_id = 1;
         let recipient = 2;
         let name = *b"8--bytes";
 
-        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
         assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id));
 
         assert_noop!(
@@ -101,10 +111,10 @@ fn it_works_for_transfer() {
 #[test]
 fn it_works_for_sale(){
     new_test_ext().execute_with(|| {
-        let kitty_id = 0;
+        let kitty_id = (DEFAULT_COLLECTION, 0);
         let account_id = 1;
 
-        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"8--bytes"));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, *b"8--bytes"));
         assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id));
 
         assert_noop!(
@@ -140,16 +150,16 @@ fn it_works_for_sale(){
 #[test]
 fn it_works_for_buy(){
     new_test_ext().execute_with(|| {
-        let kitty_id = 0;
+        let kitty_id = (DEFAULT_COLLECTION, 0);
         let account_id = 1;
         let recipient = 2;
 
         assert_noop!(
-            KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id),
+            KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id, Balance::MAX),
             Error::<Test>::InvalidKittyId
         );
 
-        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), *b"8--bytes"));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, *b"8--bytes"));
         assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id));
 
 
@@ -160,7 +170,7 @@ fn it_works_for_buy(){
         );
 
         assert_noop!(
-            KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id),
+            KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id, Balance::MAX),
             Error::<Test>::AlreadyOwned
         );
 
@@ -171,7 +181,7 @@ fn it_works_for_buy(){
             None
         );
         assert_noop!(
-            KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id),
+            KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id, Balance::MAX),
             Error::<Test>::NotOnSale
         );
 
@@ -184,7 +194,13 @@ fn it_works_for_buy(){
 
         assert_eq!(KittiesModule::kitty_owner(kitty_id),Some(recipient));
 
-        assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id));
+        // The buyer does not pay more than it agreed to.
+        let price = KittiesModule::current_price(DEFAULT_COLLECTION);
+        assert_noop!(
+            KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id, price - 1),
+            Error::<Test>::PriceTooHigh
+        );
+        assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(account_id), kitty_id, price));
         assert_eq!(KittiesModule::kitty_owner(kitty_id),Some(account_id));
 
         assert_eq!(
@@ -198,4 +214,1348 @@ fn it_works_for_buy(){
         }.into());
 
     });
-}
\ No newline at end of file
+}
+
+#[test]
+fn it_works_for_price(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let name = *b"8--bytes";
+        let kitty_price = KittyPrice::get();
+
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_eq!(Balances::free_balance(account_id), 999_999_999 - kitty_price);
+
+        // Every mint raises the price by 10%...
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price * 11 / 10);
+
+        // ...and it decays by 1% per block until it reaches the floor.
+        System::set_block_number(2);
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price * 11 / 10 * 99 / 100);
+        System::set_block_number(100);
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price);
+
+        assert_noop!(
+            KittiesModule::set_price_bounds(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, 1, 2),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            KittiesModule::set_price_bounds(RuntimeOrigin::root(), DEFAULT_COLLECTION, 2, 1),
+            Error::<Test>::InvalidPriceBounds
+        );
+        assert_noop!(
+            KittiesModule::set_price_bounds(RuntimeOrigin::root(), 1, 1, 2),
+            Error::<Test>::UnknownCollection
+        );
+
+        assert_ok!(KittiesModule::set_price_bounds(RuntimeOrigin::root(), DEFAULT_COLLECTION, kitty_price * 2, kitty_price * 3));
+        System::assert_has_event(Event::PriceBoundsSet {
+            collection_id: DEFAULT_COLLECTION,
+            floor: kitty_price * 2,
+            ceiling: kitty_price * 3,
+        }.into());
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price * 2);
+
+        for _ in 0..20 {
+            assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        }
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price * 3);
+    });
+}
+
+#[test]
+fn it_works_for_price_after_sales(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let owner = 1;
+        let bidder = 2;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), bidder, 999_999_999, 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), DEFAULT_COLLECTION, *b"8--bytes"));
+        let price = KittiesModule::current_price(DEFAULT_COLLECTION);
+
+        // An accepted offer above the mint price pulls it half way up...
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, price * 3, 10));
+        assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, bidder));
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), price * 2);
+
+        // ...a purchase at the mint price keeps it...
+        assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(bidder), kitty_id));
+        assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(owner), kitty_id, price * 2));
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), price * 2);
+
+        // ...and a cheaper one pulls it back down.
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, price, 10));
+        assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, bidder));
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), price * 3 / 2);
+    });
+}
+
+#[test]
+fn it_works_for_pot(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let recipient = 2;
+        let name = *b"8--bytes";
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::breed(
+            RuntimeOrigin::signed(account_id),
+            (DEFAULT_COLLECTION, 0),
+            (DEFAULT_COLLECTION, 1),
+            name
+        ));
+
+        // Half of the pot above the existential deposit goes to the only breed of the period.
+        let pot = KittiesModule::pot_balance();
+        let reward = (pot - EXISTENTIAL_DEPOSIT) / 2;
+        let balance = Balances::free_balance(account_id);
+        KittiesModule::on_initialize(9);
+        assert_eq!(KittiesModule::pot_balance(), pot);
+        KittiesModule::on_initialize(10);
+        System::assert_has_event(Event::BreederRewardsPaid { breeds: 1, reward }.into());
+        assert_eq!(Balances::free_balance(account_id), balance + reward);
+        assert_eq!(KittiesModule::pot_balance(), pot - reward);
+
+        // The breeds were paid once and the next period starts empty.
+        KittiesModule::on_initialize(20);
+        assert_eq!(KittiesModule::pot_balance(), pot - reward);
+
+        assert_noop!(
+            KittiesModule::spend_pot(RuntimeOrigin::signed(account_id), recipient, 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            KittiesModule::burn_pot(RuntimeOrigin::signed(account_id), 1),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        assert_ok!(KittiesModule::spend_pot(RuntimeOrigin::root(), recipient, EXISTENTIAL_DEPOSIT));
+        System::assert_has_event(Event::PotSpent { recipient, amount: EXISTENTIAL_DEPOSIT }.into());
+        assert_eq!(Balances::free_balance(recipient), EXISTENTIAL_DEPOSIT);
+
+        let issuance = Balances::total_issuance();
+        let pot = KittiesModule::pot_balance();
+        assert_ok!(KittiesModule::burn_pot(RuntimeOrigin::root(), 1));
+        System::assert_has_event(Event::PotBurned { amount: 1 }.into());
+        assert_eq!(KittiesModule::pot_balance(), pot - 1);
+        assert_eq!(Balances::total_issuance(), issuance - 1);
+    });
+}
+
+#[test]
+fn it_works_for_royalty(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let creator = 1;
+        let seller = 2;
+        let buyer = 3;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, 999_999_999, 0);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(creator), DEFAULT_COLLECTION, *b"8--bytes"));
+        assert_eq!(KittiesModule::kitty_creator(kitty_id), Some(creator));
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(creator), seller, kitty_id));
+        assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(seller), kitty_id));
+
+        let price = KittiesModule::current_price(DEFAULT_COLLECTION);
+        let creator_balance = Balances::free_balance(creator);
+        let pot = KittiesModule::pot_balance();
+        assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer), kitty_id, price));
+
+        // 10% to the creator, 5% to the pot and the rest to the seller.
+        let creator_amount = price / 10;
+        let pot_amount = price / 20;
+        System::assert_has_event(Event::RoyaltyPaid {
+            kitty_id,
+            creator: Some(creator),
+            creator_amount,
+            pot_amount,
+        }.into());
+        assert_eq!(Balances::free_balance(creator), creator_balance + creator_amount);
+        assert_eq!(KittiesModule::pot_balance(), pot + pot_amount);
+        assert_eq!(Balances::free_balance(seller), price - creator_amount - pot_amount);
+        assert_eq!(Balances::free_balance(buyer), 999_999_999 - price);
+        assert_eq!(KittiesModule::kitty_creator(kitty_id), Some(creator));
+    });
+}
+
+#[test]
+fn it_works_for_royalty_to_reaped_creator(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let creator = 4;
+        let seller = 2;
+        let buyer = 3;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), creator, 10_000, 0);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, 999_999_999, 0);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(creator), DEFAULT_COLLECTION, *b"8--bytes"));
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(creator), seller, kitty_id));
+        assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(seller), kitty_id));
+
+        // The creator account is reaped before the sale.
+        let _ = Balances::set_balance(RuntimeOrigin::root(), creator, 0, 0);
+        assert!(!System::account_exists(&creator));
+
+        let price = KittiesModule::current_price(DEFAULT_COLLECTION);
+        assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer), kitty_id, price));
+        assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(buyer));
+        assert_eq!(Balances::free_balance(creator), price / 10);
+        assert_eq!(Balances::reserved_balance(buyer), 0);
+    });
+}
+
+#[test]
+fn it_works_for_settlement(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let creator = 4;
+        let seller = 2;
+        let buyer = 3;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), creator, 10_000, 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(creator), DEFAULT_COLLECTION, *b"8--bytes"));
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(creator), seller, kitty_id));
+        assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(seller), kitty_id));
+
+        // A purchase cannot reap the buyer.
+        let price = KittiesModule::current_price(DEFAULT_COLLECTION);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, price, 0);
+        assert_noop!(
+            KittiesModule::buy(RuntimeOrigin::signed(buyer), kitty_id, price),
+            pallet_balances::Error::<Test>::KeepAlive
+        );
+
+        // Accepted offers are paid out of the reserve, also to a reaped creator and a seller
+        // without funds.
+        let amount = 10_000;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, 999_999_999, 0);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), creator, 0, 0);
+        assert!(!System::account_exists(&creator));
+        assert!(!System::account_exists(&seller));
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(buyer), kitty_id, amount, 10));
+
+        // The reserve must still be there when the offer is accepted.
+        assert_ok!(Balances::force_unreserve(RuntimeOrigin::root(), buyer, amount / 2));
+        assert_noop!(
+            KittiesModule::accept_offer(RuntimeOrigin::signed(seller), kitty_id, buyer),
+            Error::<Test>::ReserveMissing
+        );
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(buyer), kitty_id, amount, 10));
+
+        let pot = KittiesModule::pot_balance();
+        assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(seller), kitty_id, buyer));
+        assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(buyer));
+        assert_eq!(Balances::free_balance(creator), amount / 10);
+        assert_eq!(KittiesModule::pot_balance(), pot + amount / 20);
+        assert_eq!(Balances::free_balance(seller), amount - amount / 10 - amount / 20);
+        assert_eq!(Balances::reserved_balance(buyer), 0);
+        assert_eq!(Balances::free_balance(buyer), 999_999_999 - amount);
+    });
+}
+
+#[test]
+fn it_works_for_offer(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let owner = 1;
+        let bidder = 2;
+        let amount = 10_000;
+        let expiry = 10;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), bidder, 999_999_999, 0);
+
+        assert_noop!(
+            KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, amount, expiry),
+            Error::<Test>::InvalidKittyId
+        );
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), DEFAULT_COLLECTION, *b"8--bytes"));
+        assert_noop!(
+            KittiesModule::make_offer(RuntimeOrigin::signed(owner), kitty_id, amount, expiry),
+            Error::<Test>::AlreadyOwned
+        );
+
+        // The kitty does not need to be on sale, and a new offer replaces the previous one.
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, amount / 2, expiry));
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, amount, expiry));
+        System::assert_has_event(Event::OfferMade { who: bidder, kitty_id, amount }.into());
+        assert_eq!(KittiesModule::offers(kitty_id, bidder), Some(Offer { amount, expiry }));
+        assert_eq!(KittiesModule::offer_count(kitty_id), 1);
+        assert_eq!(Balances::reserved_balance(bidder), amount);
+
+        assert_ok!(KittiesModule::withdraw_offer(RuntimeOrigin::signed(bidder), kitty_id));
+        System::assert_has_event(Event::OfferWithdrawn { who: bidder, kitty_id, amount }.into());
+        assert_eq!(Balances::reserved_balance(bidder), 0);
+        assert_noop!(
+            KittiesModule::withdraw_offer(RuntimeOrigin::signed(bidder), kitty_id),
+            Error::<Test>::NoOffer
+        );
+        assert_noop!(
+            KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, bidder),
+            Error::<Test>::NoOffer
+        );
+
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, amount, expiry));
+        assert_noop!(
+            KittiesModule::accept_offer(RuntimeOrigin::signed(bidder), kitty_id, bidder),
+            Error::<Test>::NotOwner
+        );
+
+        // The owner is also the creator, so only the pot royalty is kept from the offer.
+        let owner_balance = Balances::free_balance(owner);
+        assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, bidder));
+        System::assert_has_event(Event::OfferAccepted { who: owner, buyer: bidder, kitty_id, amount }.into());
+        assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(bidder));
+        assert_eq!(KittiesModule::offers(kitty_id, bidder), None);
+        assert_eq!(KittiesModule::offer_count(kitty_id), 0);
+        assert_eq!(Balances::reserved_balance(bidder), 0);
+        assert_eq!(Balances::free_balance(bidder), 999_999_999 - amount);
+        assert_eq!(Balances::free_balance(owner), owner_balance + amount - amount / 20);
+    });
+}
+
+#[test]
+fn it_works_for_offer_limits(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let owner = 1;
+        let bidder = 4;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), bidder, 999_999_999, 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), DEFAULT_COLLECTION, *b"8--bytes"));
+
+        // Zero and dust offers cannot take the two slots of the kitty...
+        for griefer in [2, 3] {
+            let _ = Balances::set_balance(RuntimeOrigin::root(), griefer, 999_999_999, 0);
+            for dust in [0, 999] {
+                assert_noop!(
+                    KittiesModule::make_offer(RuntimeOrigin::signed(griefer), kitty_id, dust, 10),
+                    Error::<Test>::OfferTooLow
+                );
+            }
+        }
+        // ...nor hold them forever.
+        assert_noop!(
+            KittiesModule::make_offer(RuntimeOrigin::signed(2), kitty_id, 1_000, 102),
+            Error::<Test>::OfferTooLong
+        );
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), kitty_id, 1_000, 101));
+
+        // A real bid still gets in.
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, 10_000, 10));
+        assert_eq!(KittiesModule::offer_count(kitty_id), 2);
+        assert_ok!(KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, bidder));
+        assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(bidder));
+    });
+}
+
+#[test]
+fn it_works_for_offer_expiry(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let owner = 1;
+        let amount = 10_000;
+        for bidder in 2..=4 {
+            let _ = Balances::set_balance(RuntimeOrigin::root(), bidder, 999_999_999, 0);
+        }
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), DEFAULT_COLLECTION, *b"8--bytes"));
+        System::set_block_number(5);
+        assert_noop!(
+            KittiesModule::make_offer(RuntimeOrigin::signed(2), kitty_id, amount, 4),
+            Error::<Test>::OfferExpired
+        );
+
+        // At most two offers per kitty, but updating an offer does not count.
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(2), kitty_id, amount, 5));
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(3), kitty_id, amount, 20));
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(3), kitty_id, amount * 2, 20));
+        assert_noop!(
+            KittiesModule::make_offer(RuntimeOrigin::signed(4), kitty_id, amount, 20),
+            Error::<Test>::TooManyOffers
+        );
+
+        System::set_block_number(6);
+        assert_noop!(
+            KittiesModule::accept_offer(RuntimeOrigin::signed(owner), kitty_id, 2),
+            Error::<Test>::OfferExpired
+        );
+
+        // Idle blocks release the expired offer and keep the others.
+        KittiesModule::on_idle(6, Weight::MAX);
+        System::assert_has_event(Event::OfferExpired { who: 2, kitty_id, amount }.into());
+        assert_eq!(KittiesModule::offers(kitty_id, 2), None);
+        assert_eq!(Balances::reserved_balance(2), 0);
+        assert_eq!(KittiesModule::offers(kitty_id, 3), Some(Offer { amount: amount * 2, expiry: 20 }));
+        assert_eq!(KittiesModule::offer_count(kitty_id), 1);
+
+        // The freed slot can be taken by a new bidder.
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(4), kitty_id, amount, 20));
+        assert_eq!(KittiesModule::offer_count(kitty_id), 2);
+    });
+}
+
+#[test]
+fn it_works_for_lineage(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let name = *b"8--bytes";
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let breed = |item_1, item_2| {
+            KittiesModule::breed(RuntimeOrigin::signed(account_id), id(item_1), id(item_2), name)
+        };
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(breed(0, 1));
+        assert_ok!(breed(0, 1));
+        assert_eq!(KittiesModule::kitty_children(id(0)).into_inner(), vec![id(2), id(3)]);
+        assert_eq!(KittiesModule::kitty_children(id(2)).into_inner(), vec![]);
+
+        // Siblings and parents are too close.
+        assert_noop!(breed(2, 3), Error::<Test>::Inbreeding);
+        assert_noop!(breed(2, 0), Error::<Test>::Inbreeding);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(breed(2, 4));
+        let leaf = |item| Pedigree { kitty_id: id(item), parents: vec![] };
+        assert_eq!(
+            KittiesModule::lineage(id(5), 2),
+            Some(Pedigree {
+                kitty_id: id(5),
+                parents: vec![Pedigree { kitty_id: id(2), parents: vec![leaf(0), leaf(1)] }, leaf(4)],
+            })
+        );
+        assert_eq!(
+            KittiesModule::lineage(id(5), 1),
+            Some(Pedigree { kitty_id: id(5), parents: vec![leaf(2), leaf(4)] })
+        );
+        assert_eq!(KittiesModule::lineage(id(5), 0), Some(leaf(5)));
+        // Deeper requests are capped at `MaxPedigreeDepth`.
+        assert_eq!(KittiesModule::lineage(id(5), u32::MAX), KittiesModule::lineage(id(5), 2));
+        assert_eq!(KittiesModule::lineage(id(6), 1), None);
+
+        // A grandparent is still within two generations.
+        assert_noop!(breed(5, 0), Error::<Test>::Inbreeding);
+
+        assert_ok!(breed(0, 4));
+        assert_noop!(breed(0, 4), Error::<Test>::TooManyChildren);
+    });
+}
+
+#[test]
+fn it_works_for_lease(){
+    new_test_ext().execute_with(|| {
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let owner = 1;
+        let lessee = 2;
+        let other = 3;
+        let fee = 1_000;
+        let name = *b"8--bytes";
+        let _ = Balances::set_balance(RuntimeOrigin::root(), lessee, 999_999_999, 0);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), other, 999_999_999, 0);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(lessee), DEFAULT_COLLECTION, name));
+
+        assert_noop!(
+            KittiesModule::lease(RuntimeOrigin::signed(lessee), id(0), other, 10, fee),
+            Error::<Test>::NotOwner
+        );
+        assert_noop!(
+            KittiesModule::lease(RuntimeOrigin::signed(owner), id(0), lessee, 0, fee),
+            Error::<Test>::InvalidLeaseDuration
+        );
+        assert_ok!(KittiesModule::lease(RuntimeOrigin::signed(owner), id(0), lessee, 10, fee));
+        assert_ok!(KittiesModule::lease(RuntimeOrigin::signed(owner), id(1), lessee, 10, fee));
+        System::assert_has_event(Event::LeaseOffered { who: owner, kitty_id: id(0), lessee, duration: 10, fee }.into());
+
+        assert_noop!(
+            KittiesModule::accept_lease(RuntimeOrigin::signed(other), id(0)),
+            Error::<Test>::NoLeaseOffer
+        );
+        let owner_balance = Balances::free_balance(owner);
+        assert_ok!(KittiesModule::accept_lease(RuntimeOrigin::signed(lessee), id(0)));
+        System::assert_has_event(Event::LeaseStarted { who: lessee, kitty_id: id(0), expires_at: 11 }.into());
+        assert_eq!(Balances::free_balance(owner), owner_balance + fee);
+        assert_eq!(KittiesModule::kitty_owner(id(0)), Some(owner));
+        assert!(KittiesModule::can_use(id(0), &lessee));
+        assert!(!KittiesModule::can_use(id(0), &owner));
+
+        // Only one lease may end per block in the mock.
+        assert_noop!(
+            KittiesModule::accept_lease(RuntimeOrigin::signed(lessee), id(1)),
+            Error::<Test>::TooManyLeases
+        );
+
+        // The lessee breeds with the leased kitty, the owner can neither breed with it nor sell it.
+        assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(lessee), id(0), id(2), name));
+        assert_eq!(KittiesModule::kitty_owner(id(3)), Some(lessee));
+        assert_noop!(
+            KittiesModule::breed(RuntimeOrigin::signed(owner), id(0), id(1), name),
+            Error::<Test>::NotOwner
+        );
+        assert_noop!(
+            KittiesModule::transfer(RuntimeOrigin::signed(owner), other, id(0)),
+            Error::<Test>::KittyLeased
+        );
+        assert_noop!(
+            KittiesModule::sale(RuntimeOrigin::signed(owner), id(0)),
+            Error::<Test>::KittyLeased
+        );
+        assert_noop!(
+            KittiesModule::transfer(RuntimeOrigin::signed(lessee), other, id(0)),
+            Error::<Test>::NotOwner
+        );
+
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(lessee), id(0)));
+
+        // The usage rights return to the owner when the lease ends, and the lessee's entry lapses.
+        KittiesModule::on_initialize(11);
+        System::assert_has_event(Event::LeaseEnded { kitty_id: id(0), lessee }.into());
+        assert_eq!(KittiesModule::leases(id(0)), None);
+        assert!(KittiesModule::entrants().is_empty());
+        assert!(KittiesModule::can_use(id(0), &owner));
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(owner), other, id(0)));
+
+        // Terms lapse once the kitty changes hands.
+        assert_ok!(KittiesModule::lease(RuntimeOrigin::signed(other), id(0), lessee, 10, fee));
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(other), owner, id(0)));
+        assert_noop!(
+            KittiesModule::accept_lease(RuntimeOrigin::signed(lessee), id(0)),
+            Error::<Test>::NoLeaseOffer
+        );
+    });
+}
+
+#[test]
+fn it_works_for_fractionalize(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let owner = 1;
+        let co_owner = 2;
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(owner), DEFAULT_COLLECTION, *b"8--bytes"));
+        assert_noop!(
+            KittiesModule::fractionalize(RuntimeOrigin::signed(co_owner), kitty_id, 100),
+            Error::<Test>::NotOwner
+        );
+        assert_noop!(
+            KittiesModule::fractionalize(RuntimeOrigin::signed(owner), kitty_id, 0),
+            Error::<Test>::InvalidShares
+        );
+        assert_noop!(
+            KittiesModule::transfer_shares(RuntimeOrigin::signed(owner), kitty_id, co_owner, 1),
+            Error::<Test>::NotFractionalized
+        );
+
+        assert_ok!(KittiesModule::fractionalize(RuntimeOrigin::signed(owner), kitty_id, 100));
+        System::assert_has_event(Event::KittyFractionalized { who: owner, kitty_id, shares: 100 }.into());
+        // The kitty is held apart from the pot.
+        assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(KittiesModule::vault_account_id(kitty_id)));
+        assert_eq!(KittiesModule::total_shares(kitty_id), Some(100));
+        assert_eq!(KittiesModule::kitty_shares(kitty_id, owner), 100);
+
+        // The locked kitty cannot be moved, listed, leased or bid on.
+        assert_noop!(
+            KittiesModule::transfer(RuntimeOrigin::signed(owner), co_owner, kitty_id),
+            Error::<Test>::NotOwner
+        );
+        assert_noop!(
+            KittiesModule::sale(RuntimeOrigin::signed(owner), kitty_id),
+            Error::<Test>::Fractionalized
+        );
+        assert_noop!(
+            KittiesModule::lease(RuntimeOrigin::signed(owner), kitty_id, co_owner, 5, 100),
+            Error::<Test>::Fractionalized
+        );
+        assert_noop!(
+            KittiesModule::make_offer(RuntimeOrigin::signed(co_owner), kitty_id, 1_000, 10),
+            Error::<Test>::Fractionalized
+        );
+
+        assert_ok!(KittiesModule::transfer_shares(RuntimeOrigin::signed(owner), kitty_id, co_owner, 40));
+        System::assert_has_event(Event::SharesTransferred { kitty_id, from: owner, to: co_owner, amount: 40 }.into());
+        assert_noop!(
+            KittiesModule::transfer_shares(RuntimeOrigin::signed(co_owner), kitty_id, owner, 41),
+            Error::<Test>::InsufficientShares
+        );
+        assert_noop!(
+            KittiesModule::redeem(RuntimeOrigin::signed(owner), kitty_id),
+            Error::<Test>::InsufficientShares
+        );
+
+        assert_ok!(KittiesModule::transfer_shares(RuntimeOrigin::signed(owner), kitty_id, co_owner, 60));
+        assert_eq!(KittiesModule::kitty_shares(kitty_id, owner), 0);
+        assert_ok!(KittiesModule::redeem(RuntimeOrigin::signed(co_owner), kitty_id));
+        System::assert_has_event(Event::KittyRedeemed { who: co_owner, kitty_id }.into());
+        assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(co_owner));
+        assert_eq!(KittiesModule::total_shares(kitty_id), None);
+        assert_eq!(KittiesModule::kitty_shares(kitty_id, co_owner), 0);
+    });
+}
+
+#[test]
+fn it_works_for_competition(){
+    new_test_ext().execute_with(|| {
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let name = *b"8--bytes";
+        let fee = KittyPrice::get();
+        let _ = Balances::set_balance(RuntimeOrigin::root(), 2, 999_999_999, 0);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(2), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), DEFAULT_COLLECTION, name));
+
+        assert_noop!(
+            KittiesModule::enter_competition(RuntimeOrigin::signed(1), id(1)),
+            Error::<Test>::NotOwner
+        );
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(1), id(0)));
+        System::assert_has_event(Event::KittyEntered { who: 1, kitty_id: id(0) }.into());
+        assert_noop!(
+            KittiesModule::enter_competition(RuntimeOrigin::signed(1), id(0)),
+            Error::<Test>::AlreadyEntered
+        );
+
+        // A single entrant waits for an opponent.
+        KittiesModule::on_initialize(5);
+        assert_eq!(KittiesModule::entrants().len(), 1);
+
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(2), id(1)));
+        assert_noop!(
+            KittiesModule::enter_competition(RuntimeOrigin::signed(1), id(2)),
+            Error::<Test>::CompetitionFull
+        );
+
+        let balances = [Balances::free_balance(1), Balances::free_balance(2)];
+        KittiesModule::on_initialize(10);
+        assert!(KittiesModule::entrants().is_empty());
+
+        // The winner takes both entry fees and gains a rating point for its opponent.
+        let (kitty_id, winner, loser) =
+            if KittiesModule::kitty_rating(id(0)) == 1 { (id(0), 1, id(1)) } else { (id(1), 2, id(0)) };
+        assert_eq!(KittiesModule::kitty_rating(kitty_id), 1);
+        assert_eq!(KittiesModule::kitty_rating(loser), 0);
+        System::assert_has_event(Event::CompetitionWon { kitty_id, winner, prize: fee * 2, entrants: 2 }.into());
+        assert_eq!(Balances::free_balance(winner), balances[winner as usize - 1] + fee * 2);
+    });
+}
+
+#[test]
+fn it_works_for_competition_after_transfer(){
+    new_test_ext().execute_with(|| {
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let name = *b"8--bytes";
+        let _ = Balances::set_balance(RuntimeOrigin::root(), 2, 999_999_999, 0);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), 3, 999_999_999, 0);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(2), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(1), id(0)));
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(2), id(1)));
+
+        // The entry of the previous owner lapses; the new owner can enter again.
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(1), 3, id(0)));
+        assert_eq!(KittiesModule::entrants().into_inner(), vec![(id(1), 2)]);
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(3), id(0)));
+
+        KittiesModule::on_initialize(5);
+        let winner = if KittiesModule::kitty_rating(id(0)) == 1 { 3 } else { 2 };
+        System::assert_has_event(Event::CompetitionWon {
+            kitty_id: if winner == 3 { id(0) } else { id(1) },
+            winner,
+            prize: KittyPrice::get() * 3,
+            entrants: 2,
+        }.into());
+    });
+}
+
+#[test]
+fn it_works_for_competition_stats(){
+    new_test_ext().execute_with(|| {
+        let (strong, weak) = ((DEFAULT_COLLECTION, 0), (DEFAULT_COLLECTION, 1));
+        let name = *b"8--bytes";
+        let _ = Balances::set_balance(RuntimeOrigin::root(), 2, 999_999_999, 0);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(2), DEFAULT_COLLECTION, name));
+        crate::Kitties::<Test>::mutate(strong, |kitty| kitty.as_mut().unwrap().dna = [192; 16]);
+        crate::Kitties::<Test>::mutate(weak, |kitty| kitty.as_mut().unwrap().dna = [64; 16]);
+
+        // The roll spans the same range as the stats, so the stronger kitty wins most rounds.
+        for round in 1..=20 {
+            assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(1), strong));
+            assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(2), weak));
+            KittiesModule::on_initialize(round * 5);
+        }
+        assert_eq!(KittiesModule::kitty_rating(strong) + KittiesModule::kitty_rating(weak), 20);
+        assert!(KittiesModule::kitty_rating(strong) > KittiesModule::kitty_rating(weak));
+    });
+}
+
+#[test]
+fn it_works_for_collections(){
+    new_test_ext().execute_with(|| {
+        let admin = 2;
+        let name = *b"8--bytes";
+        let config = CollectionConfig {
+            mint_price: 1_000,
+            max_supply: Some(2),
+            mint_window: Some((1, 10)),
+            breeding: BreedingRule::SameCollection,
+        };
+        let _ = Balances::set_balance(RuntimeOrigin::root(), admin, 999_999_999, 0);
+
+        assert_noop!(
+            KittiesModule::create_collection(RuntimeOrigin::signed(admin), config.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            KittiesModule::set_collection_admin(RuntimeOrigin::signed(admin), admin, true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(KittiesModule::set_collection_admin(RuntimeOrigin::root(), admin, true));
+        System::assert_has_event(Event::CollectionAdminSet { who: admin, is_admin: true }.into());
+
+        assert_ok!(KittiesModule::create_collection(RuntimeOrigin::signed(admin), config.clone()));
+        System::assert_has_event(Event::CollectionCreated { collection_id: 1, admin: Some(admin) }.into());
+        assert_noop!(
+            KittiesModule::create(RuntimeOrigin::signed(1), 2, name),
+            Error::<Test>::UnknownCollection
+        );
+
+        // Each collection has its own price, supply and ids.
+        assert_eq!(KittiesModule::current_price(1), 1_000);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), 1, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), 1, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), DEFAULT_COLLECTION, name));
+        assert_eq!(KittiesModule::current_price(1), 1_210);
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), KittyPrice::get() * 11 / 10);
+        assert_eq!(KittiesModule::kitty_owner((1, 1)), Some(1));
+        assert_eq!(KittiesModule::kitty_owner((DEFAULT_COLLECTION, 0)), Some(1));
+        assert_noop!(
+            KittiesModule::create(RuntimeOrigin::signed(1), 1, name),
+            Error::<Test>::SupplyExhausted
+        );
+        assert_noop!(
+            KittiesModule::breed(RuntimeOrigin::signed(1), (1, 0), (DEFAULT_COLLECTION, 0), name),
+            Error::<Test>::BreedingNotAllowed
+        );
+
+        // Only root or the admin of the collection may change it.
+        assert_noop!(
+            KittiesModule::update_collection(RuntimeOrigin::signed(3), 1, config.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_noop!(
+            KittiesModule::update_collection(RuntimeOrigin::signed(admin), DEFAULT_COLLECTION, config.clone()),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(KittiesModule::update_collection(
+            RuntimeOrigin::signed(admin),
+            1,
+            CollectionConfig { max_supply: Some(3), ..config.clone() }
+        ));
+        System::assert_has_event(Event::CollectionUpdated { collection_id: 1 }.into());
+
+        assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(1), (1, 0), (1, 1), name));
+        assert_eq!(KittiesModule::kitty_parents((1, 2)), Some(((1, 0), (1, 1))));
+
+        assert_ok!(KittiesModule::update_collection(RuntimeOrigin::root(), 1, config));
+        System::set_block_number(11);
+        assert_noop!(
+            KittiesModule::create(RuntimeOrigin::signed(1), 1, name),
+            Error::<Test>::MintWindowClosed
+        );
+    });
+}
+
+#[test]
+fn it_works_for_price_bounds_per_collection(){
+    new_test_ext().execute_with(|| {
+        let name = *b"8--bytes";
+        let kitty_price = KittyPrice::get();
+        let config = CollectionConfig {
+            mint_price: 1_000,
+            max_supply: None,
+            mint_window: None,
+            breeding: BreedingRule::Open,
+        };
+        assert_ok!(KittiesModule::create_collection(RuntimeOrigin::root(), config));
+
+        assert_ok!(KittiesModule::set_price_bounds(RuntimeOrigin::root(), 1, 2_000, 3_000));
+        System::assert_has_event(Event::PriceBoundsSet { collection_id: 1, floor: 2_000, ceiling: 3_000 }.into());
+        assert_eq!(KittiesModule::current_price(1), 2_000);
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price);
+
+        assert_ok!(KittiesModule::set_price_bounds(RuntimeOrigin::root(), DEFAULT_COLLECTION, kitty_price * 2, kitty_price * 3));
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price * 2);
+        assert_eq!(KittiesModule::current_price(1), 2_000);
+
+        // Mints only push the price of their own collection, up to its own ceiling.
+        for _ in 0..10 {
+            assert_ok!(KittiesModule::create(RuntimeOrigin::signed(1), 1, name));
+        }
+        assert_eq!(KittiesModule::current_price(1), 3_000);
+        assert_eq!(KittiesModule::current_price(DEFAULT_COLLECTION), kitty_price * 2);
+    });
+}
+
+#[test]
+fn it_works_for_mint_limits(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let buyer = 2;
+        let name = *b"8--bytes";
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, 999_999_999, 0);
+
+        // Presale: only allowlisted accounts can create.
+        assert_noop!(
+            KittiesModule::set_allowlist_only(RuntimeOrigin::signed(account_id), true),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(KittiesModule::set_allowlist_only(RuntimeOrigin::root(), true));
+        System::assert_has_event(Event::AllowlistOnlySet { enabled: true }.into());
+        assert_noop!(
+            KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name),
+            Error::<Test>::NotAllowlisted
+        );
+        assert_ok!(KittiesModule::set_allowlisted(RuntimeOrigin::root(), account_id, true));
+        System::assert_has_event(Event::AllowlistUpdated { who: account_id, allowed: true }.into());
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::set_allowlist_only(RuntimeOrigin::root(), false));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(buyer), DEFAULT_COLLECTION, name));
+
+        // At most 25 creates per account and period.
+        for _ in 1..25 {
+            assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        }
+        assert_noop!(
+            KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name),
+            Error::<Test>::MintLimitReached
+        );
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(buyer), DEFAULT_COLLECTION, name));
+        System::set_block_number(10);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_eq!(KittiesModule::total_supply(), 28);
+
+        // Breeding counts towards the global supply too.
+        crate::TotalSupply::<Test>::put(100);
+        assert_noop!(
+            KittiesModule::create(RuntimeOrigin::signed(buyer), DEFAULT_COLLECTION, name),
+            Error::<Test>::MaxSupplyReached
+        );
+        assert_noop!(
+            KittiesModule::breed(
+                RuntimeOrigin::signed(account_id),
+                (DEFAULT_COLLECTION, 0),
+                (DEFAULT_COLLECTION, 2),
+                name
+            ),
+            Error::<Test>::MaxSupplyReached
+        );
+    });
+}
+
+#[test]
+fn it_works_for_soulbound(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let buyer = 2;
+        let name = *b"8--bytes";
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, 999_999_999, 0);
+
+        assert_noop!(
+            KittiesModule::create_soulbound(RuntimeOrigin::signed(account_id), account_id, DEFAULT_COLLECTION, name),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(KittiesModule::create_soulbound(RuntimeOrigin::root(), account_id, DEFAULT_COLLECTION, name));
+        assert_eq!(KittiesModule::kitty_owner(kitty_id), Some(account_id));
+        assert_eq!(KittiesModule::is_transferable(kitty_id), false);
+        assert_eq!(KittiesModule::total_supply(), 1);
+
+        assert_noop!(
+            KittiesModule::transfer(RuntimeOrigin::signed(account_id), buyer, kitty_id),
+            Error::<Test>::NotTransferable
+        );
+        assert_noop!(
+            KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id),
+            Error::<Test>::NotTransferable
+        );
+        assert_noop!(
+            KittiesModule::make_offer(RuntimeOrigin::signed(buyer), kitty_id, 1_000, 10),
+            Error::<Test>::NotTransferable
+        );
+        assert_noop!(
+            KittiesModule::fractionalize(RuntimeOrigin::signed(account_id), kitty_id, 100),
+            Error::<Test>::NotTransferable
+        );
+
+        // Kitties created by anyone else stay transferable.
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_eq!(KittiesModule::is_transferable((DEFAULT_COLLECTION, 1)), true);
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), buyer, (DEFAULT_COLLECTION, 1)));
+    });
+}
+
+#[test]
+fn it_works_for_attributes(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let buyer = 2;
+        let name = *b"8--bytes";
+        let kitty_id = (DEFAULT_COLLECTION, 12);
+        let key = |key: &[u8]| key.to_vec().try_into().unwrap();
+        let value = |value: &[u8]| value.to_vec().try_into().unwrap();
+        crate::NextItemId::<Test>::insert(DEFAULT_COLLECTION, 12);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+
+        // Owner attributes are editable by the owner only.
+        assert_noop!(
+            KittiesModule::set_attribute(RuntimeOrigin::signed(buyer), kitty_id, AttributeNamespace::Owner, key(b"color"), value(b"red")),
+            Error::<Test>::NotOwner
+        );
+        assert_ok!(KittiesModule::set_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Owner, key(b"color"), value(b"red")));
+        System::assert_last_event(Event::AttributeSet {
+            kitty_id, namespace: AttributeNamespace::Owner, key: key(b"color"), value: value(b"red")
+        }.into());
+
+        // Issuer attributes are locked for the owner.
+        assert_noop!(
+            KittiesModule::set_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Issuer, key(b"rarity"), value(b"epic")),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(KittiesModule::set_attribute(RuntimeOrigin::root(), kitty_id, AttributeNamespace::Issuer, key(b"rarity"), value(b"epic")));
+        assert_noop!(
+            KittiesModule::clear_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Issuer, key(b"rarity")),
+            sp_runtime::DispatchError::BadOrigin
+        );
+
+        // At most 2 attributes per kitty.
+        assert_noop!(
+            KittiesModule::set_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Owner, key(b"eyes"), value(b"blue")),
+            Error::<Test>::TooManyAttributes
+        );
+        assert_ok!(KittiesModule::set_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Owner, key(b"color"), value(b"blue")));
+        assert_eq!(KittiesModule::kitty_attributes(kitty_id).len(), 2);
+        assert_ok!(KittiesModule::clear_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Owner, key(b"color")));
+        assert_noop!(
+            KittiesModule::clear_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Owner, key(b"color")),
+            Error::<Test>::NoAttribute
+        );
+        assert_ok!(KittiesModule::set_attribute(RuntimeOrigin::signed(account_id), kitty_id, AttributeNamespace::Owner, key(b"eyes"), value(b"blue")));
+        let attributes = KittiesModule::kitty_attributes(kitty_id);
+        assert_eq!(attributes.len(), 2);
+        assert!(attributes.contains(&(AttributeNamespace::Issuer, b"rarity".to_vec(), b"epic".to_vec())));
+        assert!(attributes.contains(&(AttributeNamespace::Owner, b"eyes".to_vec(), b"blue".to_vec())));
+
+        // Token URIs need a base URI.
+        assert_eq!(KittiesModule::token_uri(kitty_id), None);
+        assert_noop!(
+            KittiesModule::set_base_uri(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, Some(b"ipfs://kitties/".to_vec().try_into().unwrap())),
+            sp_runtime::DispatchError::BadOrigin
+        );
+        assert_ok!(KittiesModule::set_base_uri(RuntimeOrigin::root(), DEFAULT_COLLECTION, Some(b"ipfs://kitties/".to_vec().try_into().unwrap())));
+        assert_eq!(KittiesModule::token_uri(kitty_id), Some(b"ipfs://kitties/12".to_vec()));
+        assert_eq!(KittiesModule::token_uri((DEFAULT_COLLECTION, 13)), None);
+        assert_ok!(KittiesModule::set_base_uri(RuntimeOrigin::root(), DEFAULT_COLLECTION, None));
+        assert_eq!(KittiesModule::token_uri(kitty_id), None);
+    });
+}
+
+#[test]
+fn it_works_for_batches(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let recipient = 2;
+        let buyer = 3;
+        let name = *b"8--bytes";
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, 999_999_999, 0);
+        for _ in 0..4 {
+            assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        }
+
+        // All or nothing: the unknown kitty reverts the first transfer.
+        assert_noop!(
+            KittiesModule::transfer_batch(
+                RuntimeOrigin::signed(account_id),
+                vec![(recipient, id(0)), (recipient, id(9))].try_into().unwrap()
+            ),
+            Error::<Test>::InvalidKittyId
+        );
+        assert_ok!(KittiesModule::transfer_batch(
+            RuntimeOrigin::signed(account_id),
+            vec![(recipient, id(0)), (buyer, id(1))].try_into().unwrap()
+        ));
+        assert_eq!(KittiesModule::kitty_owner(id(0)), Some(recipient));
+        assert_eq!(KittiesModule::kitty_owner(id(1)), Some(buyer));
+
+        assert_noop!(
+            KittiesModule::sale_batch(RuntimeOrigin::signed(account_id), vec![id(2), id(3), id(0)].try_into().unwrap()),
+            Error::<Test>::NotOwner
+        );
+        assert_ok!(KittiesModule::sale_batch(RuntimeOrigin::signed(account_id), vec![id(2), id(3)].try_into().unwrap()));
+        assert!(KittiesModule::kitty_on_sale(id(2)).is_some());
+        assert!(KittiesModule::kitty_on_sale(id(3)).is_some());
+
+        assert_noop!(
+            KittiesModule::buy_batch(RuntimeOrigin::signed(buyer), vec![(id(2), Balance::MAX), (id(1), Balance::MAX)].try_into().unwrap()),
+            Error::<Test>::AlreadyOwned
+        );
+        assert_ok!(KittiesModule::buy_batch(RuntimeOrigin::signed(buyer), vec![(id(2), Balance::MAX), (id(3), Balance::MAX)].try_into().unwrap()));
+        assert_eq!(KittiesModule::kitty_owner(id(2)), Some(buyer));
+        assert_eq!(KittiesModule::kitty_owner(id(3)), Some(buyer));
+        assert!(KittiesModule::kitty_on_sale(id(3)).is_none());
+
+        // Even an empty batch pays a base weight.
+        use frame_support::dispatch::GetDispatchInfo;
+        let weight = |call: crate::Call<Test>| call.get_dispatch_info().weight.ref_time();
+        assert_eq!(weight(crate::Call::transfer_batch { transfers: Default::default() }), 10_000);
+        assert_eq!(weight(crate::Call::sale_batch { kitty_ids: Default::default() }), 10_000);
+        assert_eq!(weight(crate::Call::buy_batch { purchases: vec![(id(2), 1)].try_into().unwrap() }), 20_000);
+    });
+}
+
+#[test]
+fn it_works_for_swap(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let counterparty = 2;
+        let name = *b"8--bytes";
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let kitties = |ids: Vec<_>| -> frame_support::BoundedVec<_, _> { ids.try_into().unwrap() };
+        let _ = Balances::set_balance(RuntimeOrigin::root(), counterparty, 999_999_999, 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(counterparty), DEFAULT_COLLECTION, name));
+
+        assert_noop!(
+            KittiesModule::propose_swap(RuntimeOrigin::signed(account_id), kitties(vec![id(0), id(0)]), kitties(vec![id(2)]), counterparty, None, 10),
+            Error::<Test>::InvalidSwap
+        );
+        assert_noop!(
+            KittiesModule::propose_swap(RuntimeOrigin::signed(account_id), kitties(vec![id(0)]), kitties(vec![id(1)]), counterparty, None, 10),
+            Error::<Test>::NotOwner
+        );
+
+        // Two kitties and a top-up for one kitty.
+        let free = Balances::free_balance(account_id);
+        assert_ok!(KittiesModule::propose_swap(
+            RuntimeOrigin::signed(account_id), kitties(vec![id(0), id(1)]), kitties(vec![id(2)]), counterparty, Some(1_000), 10
+        ));
+        System::assert_last_event(Event::SwapProposed { swap_id: 0, proposer: account_id, counterparty, expiry: 10 }.into());
+        assert_eq!(Balances::reserved_balance(account_id), 1_000);
+        assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), id(1)));
+
+        assert_noop!(KittiesModule::accept_swap(RuntimeOrigin::signed(account_id), 0), Error::<Test>::NoSwap);
+        let counterparty_free = Balances::free_balance(counterparty);
+        assert_ok!(KittiesModule::accept_swap(RuntimeOrigin::signed(counterparty), 0));
+        System::assert_last_event(Event::SwapAccepted { swap_id: 0, proposer: account_id, counterparty }.into());
+        assert_eq!(KittiesModule::kitty_owner(id(0)), Some(counterparty));
+        assert_eq!(KittiesModule::kitty_owner(id(1)), Some(counterparty));
+        assert_eq!(KittiesModule::kitty_owner(id(2)), Some(account_id));
+        assert!(KittiesModule::kitty_on_sale(id(1)).is_none());
+        assert_eq!(Balances::free_balance(account_id), free - 1_000);
+        assert_eq!(Balances::reserved_balance(account_id), 0);
+        assert_eq!(Balances::free_balance(counterparty), counterparty_free + 1_000);
+        assert!(KittiesModule::swaps(0).is_none());
+
+        // Expired swaps cannot be accepted, and anyone can clear them.
+        assert_ok!(KittiesModule::propose_swap(
+            RuntimeOrigin::signed(account_id), kitties(vec![id(2)]), kitties(vec![id(0)]), counterparty, Some(500), 10
+        ));
+        assert_noop!(KittiesModule::cancel_swap(RuntimeOrigin::signed(3), 1), sp_runtime::DispatchError::BadOrigin);
+        System::set_block_number(11);
+        assert_noop!(KittiesModule::accept_swap(RuntimeOrigin::signed(counterparty), 1), Error::<Test>::SwapExpired);
+        assert_ok!(KittiesModule::cancel_swap(RuntimeOrigin::signed(3), 1));
+        System::assert_last_event(Event::SwapCancelled { swap_id: 1 }.into());
+        assert_eq!(Balances::reserved_balance(account_id), 0);
+        assert_noop!(KittiesModule::cancel_swap(RuntimeOrigin::signed(account_id), 1), Error::<Test>::NoSwap);
+    });
+}
+
+#[test]
+fn it_works_for_swap_with_missing_reserve(){
+    use frame_support::traits::ReservableCurrency;
+
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let counterparty = 2;
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let kitties = |ids: Vec<_>| -> frame_support::BoundedVec<_, _> { ids.try_into().unwrap() };
+        let _ = Balances::set_balance(RuntimeOrigin::root(), counterparty, 999_999_999, 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, *b"8--bytes"));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(counterparty), DEFAULT_COLLECTION, *b"8--bytes"));
+        assert_ok!(KittiesModule::propose_swap(
+            RuntimeOrigin::signed(account_id), kitties(vec![id(0)]), kitties(vec![id(1)]), counterparty, Some(1_000), 10
+        ));
+
+        // Part of the top-up is released behind the pallet's back: the swap cannot go through.
+        Balances::unreserve(&account_id, 600);
+        assert_noop!(KittiesModule::accept_swap(RuntimeOrigin::signed(counterparty), 0), Error::<Test>::ReserveMissing);
+        assert_eq!(KittiesModule::kitty_owner(id(0)), Some(account_id));
+        assert_eq!(KittiesModule::kitty_owner(id(1)), Some(counterparty));
+        assert_eq!(Balances::reserved_balance(account_id), 400);
+        assert!(KittiesModule::swaps(0).is_some());
+    });
+}
+
+#[test]
+fn it_works_for_listing_cleanup(){
+    new_test_ext().execute_with(|| {
+        let kitty_id = (DEFAULT_COLLECTION, 0);
+        let account_id = 1;
+        let recipient = 2;
+        let bidder = 3;
+        let _ = Balances::set_balance(RuntimeOrigin::root(), bidder, 999_999_999, 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, *b"8--bytes"));
+
+        // Listings, lease terms and offers made for the old owner lapse on transfer.
+        assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), kitty_id));
+        assert_ok!(KittiesModule::lease(RuntimeOrigin::signed(account_id), kitty_id, bidder, 5, 100));
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(bidder), kitty_id, 1_000, 10));
+        assert_eq!(Balances::reserved_balance(bidder), 1_000);
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), recipient, kitty_id));
+
+        assert_eq!(KittiesModule::kitty_on_sale(kitty_id), None);
+        assert!(KittiesModule::lease_offers(kitty_id).is_none());
+        assert_eq!(KittiesModule::offers(kitty_id, bidder), None);
+        assert_eq!(Balances::reserved_balance(bidder), 0);
+        System::assert_has_event(Event::OfferWithdrawn { who: bidder, kitty_id, amount: 1_000 }.into());
+        assert_noop!(
+            KittiesModule::buy(RuntimeOrigin::signed(bidder), kitty_id, Balance::MAX),
+            Error::<Test>::NotOnSale
+        );
+    });
+}
+
+#[test]
+fn it_works_for_kitty_event_hooks(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let buyer = 2;
+        let name = *b"8--bytes";
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let _ = Balances::set_balance(RuntimeOrigin::root(), buyer, 999_999_999, 0);
+
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::breed(RuntimeOrigin::signed(account_id), id(0), id(1), name));
+        assert_ok!(KittiesModule::transfer(RuntimeOrigin::signed(account_id), buyer, id(2)));
+        assert_ok!(KittiesModule::sale(RuntimeOrigin::signed(account_id), id(0)));
+        let price = KittiesModule::current_price(DEFAULT_COLLECTION);
+        assert_ok!(KittiesModule::buy(RuntimeOrigin::signed(buyer), id(0), price));
+
+        assert_eq!(kitty_events(), vec![
+            KittyEvent::Created(account_id, id(0)),
+            KittyEvent::Created(account_id, id(1)),
+            KittyEvent::Bred(account_id, id(2)),
+            KittyEvent::Transferred(account_id, buyer, id(2)),
+            KittyEvent::Listed(account_id, id(0)),
+            KittyEvent::Transferred(account_id, buyer, id(0)),
+            KittyEvent::Sold(account_id, buyer, id(0), price),
+        ]);
+    });
+}
+
+#[test]
+fn it_works_for_kitty_event_hooks_on_markets(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let other = 2;
+        let name = *b"8--bytes";
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let kitties = |ids: Vec<_>| -> frame_support::BoundedVec<_, _> { ids.try_into().unwrap() };
+        let _ = Balances::set_balance(RuntimeOrigin::root(), other, 999_999_999, 0);
+        System::set_block_number(1);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(other), DEFAULT_COLLECTION, name));
+        let events = kitty_events().len();
+
+        // Listings and offers.
+        assert_ok!(KittiesModule::sale_batch(RuntimeOrigin::signed(account_id), kitties(vec![id(0)])));
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(other), id(0), 1_000, 2));
+        assert_ok!(KittiesModule::withdraw_offer(RuntimeOrigin::signed(other), id(0)));
+        assert_ok!(KittiesModule::make_offer(RuntimeOrigin::signed(other), id(0), 2_000, 2));
+        System::set_block_number(3);
+        KittiesModule::on_idle(3, Weight::MAX);
+
+        // Leases.
+        assert_ok!(KittiesModule::lease(RuntimeOrigin::signed(account_id), id(0), other, 2, 100));
+        assert_ok!(KittiesModule::accept_lease(RuntimeOrigin::signed(other), id(0)));
+        KittiesModule::on_initialize(5);
+
+        // Fractionalization.
+        let vault = KittiesModule::vault_account_id(id(0));
+        assert_ok!(KittiesModule::fractionalize(RuntimeOrigin::signed(account_id), id(0), 10));
+        assert_ok!(KittiesModule::redeem(RuntimeOrigin::signed(account_id), id(0)));
+
+        // Swaps.
+        assert_ok!(KittiesModule::propose_swap(
+            RuntimeOrigin::signed(account_id), kitties(vec![id(0)]), kitties(vec![id(1)]), other, Some(1_000), 10
+        ));
+        assert_ok!(KittiesModule::accept_swap(RuntimeOrigin::signed(other), 0));
+        assert_ok!(KittiesModule::propose_swap(
+            RuntimeOrigin::signed(account_id), kitties(vec![id(1)]), kitties(vec![id(0)]), other, None, 10
+        ));
+        assert_ok!(KittiesModule::cancel_swap(RuntimeOrigin::signed(other), 1));
+
+        assert_eq!(kitty_events()[events..], vec![
+            KittyEvent::Listed(account_id, id(0)),
+            KittyEvent::OfferMade(other, id(0), 1_000),
+            KittyEvent::OfferRemoved(other, id(0), 1_000),
+            KittyEvent::OfferMade(other, id(0), 2_000),
+            KittyEvent::OfferRemoved(other, id(0), 2_000),
+            KittyEvent::LeaseOffered(account_id, other, id(0)),
+            KittyEvent::Leased(account_id, other, id(0)),
+            KittyEvent::LeaseEnded(other, id(0)),
+            KittyEvent::Transferred(account_id, vault, id(0)),
+            KittyEvent::Fractionalized(account_id, id(0), 10),
+            KittyEvent::Transferred(vault, account_id, id(0)),
+            KittyEvent::Redeemed(account_id, id(0)),
+            KittyEvent::SwapProposed(account_id, other, 0),
+            KittyEvent::Transferred(account_id, other, id(0)),
+            KittyEvent::Transferred(other, account_id, id(1)),
+            KittyEvent::Swapped(account_id, other, vec![id(0)], vec![id(1)], 1_000),
+            KittyEvent::SwapProposed(account_id, other, 1),
+            KittyEvent::SwapCancelled(account_id, other, 1),
+        ][..]);
+    });
+}
+
+#[test]
+fn it_works_for_kitty_event_hooks_on_other_calls(){
+    new_test_ext().execute_with(|| {
+        let account_id = 1;
+        let other = 2;
+        let name = *b"8--bytes";
+        let id = |item| (DEFAULT_COLLECTION, item);
+        let key: frame_support::BoundedVec<_, _> = b"color".to_vec().try_into().unwrap();
+        let config = CollectionConfig {
+            mint_price: 1_000,
+            max_supply: None,
+            mint_window: None,
+            breeding: BreedingRule::Open,
+        };
+        let _ = Balances::set_balance(RuntimeOrigin::root(), other, 999_999_999, 0);
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(other), DEFAULT_COLLECTION, name));
+        assert_ok!(KittiesModule::create(RuntimeOrigin::signed(account_id), DEFAULT_COLLECTION, name));
+        let events = kitty_events().len();
+
+        // Competitions.
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(account_id), id(0)));
+        assert_ok!(KittiesModule::enter_competition(RuntimeOrigin::signed(other), id(1)));
+        KittiesModule::on_initialize(5);
+        let (winner, kitty_id) = if KittiesModule::kitty_rating(id(0)) == 1 { (account_id, id(0)) } else { (other, id(1)) };
+
+        // Shares.
+        assert_ok!(KittiesModule::fractionalize(RuntimeOrigin::signed(account_id), id(2), 10));
+        assert_ok!(KittiesModule::transfer_shares(RuntimeOrigin::signed(account_id), id(2), other, 4));
+
+        // Attributes.
+        assert_ok!(KittiesModule::set_attribute(
+            RuntimeOrigin::signed(account_id), id(0), AttributeNamespace::Owner, key.clone(), b"red".to_vec().try_into().unwrap()
+        ));
+        assert_ok!(KittiesModule::clear_attribute(RuntimeOrigin::signed(account_id), id(0), AttributeNamespace::Owner, key.clone()));
+
+        // Collections.
+        assert_ok!(KittiesModule::create_collection(RuntimeOrigin::root(), config.clone()));
+        assert_ok!(KittiesModule::update_collection(RuntimeOrigin::root(), 1, config));
+
+        let vault = KittiesModule::vault_account_id(id(2));
+        assert_eq!(kitty_events()[events..], vec![
+            KittyEvent::Entered(account_id, id(0)),
+            KittyEvent::Entered(other, id(1)),
+            KittyEvent::CompetitionWon(winner, kitty_id, KittyPrice::get() * 2, 1),
+            KittyEvent::Transferred(account_id, vault, id(2)),
+            KittyEvent::Fractionalized(account_id, id(2), 10),
+            KittyEvent::SharesTransferred(account_id, other, id(2), 4),
+            KittyEvent::AttributeSet(id(0), AttributeNamespace::Owner, key.to_vec()),
+            KittyEvent::AttributeCleared(id(0), AttributeNamespace::Owner, key.into_inner()),
+            KittyEvent::CollectionCreated(1, None),
+            KittyEvent::CollectionUpdated(1),
+        ][..]);
+    });
+}
+
+#[test]
+fn it_works_for_genesis_config(){
+    use frame_support::traits::GenesisBuild;
+
+    let mut storage = frame_system::GenesisConfig::default().build_storage::<Test>().unwrap();
+    crate::GenesisConfig::<Test> {
+        kitties: vec![(1, *b"genesis1", Some([7; 16])), (2, *b"genesis2", None)],
+        next_item_id: 5,
+        on_sale: vec![(DEFAULT_COLLECTION, 6)],
+    }
+    .assimilate_storage(&mut storage)
+    .unwrap();
+
+    sp_io::TestExternalities::from(storage).execute_with(|| {
+        let id = |item| (DEFAULT_COLLECTION, item);
+        assert_eq!(KittiesModule::kitties(id(5)).map(|kitty| kitty.dna), Some([7; 16]));
+        assert_eq!(KittiesModule::kitties(id(6)).map(|kitty| kitty.name), Some(*b"genesis2"));
+        assert_eq!(KittiesModule::kitty_owner(id(5)), Some(1));
+        assert_eq!(KittiesModule::kitty_owner(id(6)), Some(2));
+        assert_eq!(KittiesModule::kitty_on_sale(id(5)), None);
+        assert_eq!(KittiesModule::kitty_on_sale(id(6)), Some(()));
+        assert_eq!(KittiesModule::next_item_id(DEFAULT_COLLECTION), 7);
+        assert_eq!(KittiesModule::total_supply(), 2);
+    });
+}
+
+#[test]
+fn it_works_for_v2_migration(){
+    use crate::{migrations::{upgrade_storage, v2}, Kitties, Lease};
+    use codec::Encode;
+    use frame_support::{
+        migration::put_storage_value,
+        traits::{GetStorageVersion, StorageVersion},
+        Blake2_128Concat, StorageHasher, StoragePrefixedMap,
+    };
+
+    new_test_ext().execute_with(|| {
+        let (owner, bidder) = (1u64, 2u64);
+        let kitty = v2::Kitty { name: *b"8--bytes", dna: [3; 16] };
+        let offer = Offer { amount: 1_000u128, expiry: 10u64 };
+        let lease = Lease { lessee: bidder, expires_at: 20u64 };
+        let module = Kitties::<Test>::module_prefix();
+        let key = |kitty_id: v2::KittyId| Blake2_128Concat::hash(&kitty_id.encode());
+
+        // v2 layout: maps keyed by plain `u32` kitty ids.
+        for kitty_id in [0u32, 1] {
+            put_storage_value(module, b"Kitties", &key(kitty_id), kitty.clone());
+            put_storage_value(module, b"KittyOwner", &key(kitty_id), owner);
+        }
+        put_storage_value(module, b"KittyOnSale", &key(1), ());
+        put_storage_value(module, b"KittyChildren", &key(0), vec![1u32]);
+        put_storage_value(module, b"Offers", &[key(1), Blake2_128Concat::hash(&bidder.encode())].concat(), offer.clone());
+        put_storage_value(module, b"OfferCount", &key(1), 1u32);
+        put_storage_value(module, b"Leases", &key(0), lease.clone());
+        put_storage_value(module, b"KittyRatings", &key(1), 4u32);
+        put_storage_value(module, b"NextKittyId", &[], 2u32);
+        put_storage_value(module, b"PriceBounds", &[], (1_000u128, 9_000u128));
+        StorageVersion::new(2).put::<KittiesModule>();
+
+        upgrade_storage::<Test>();
+
+        let id = |item| (DEFAULT_COLLECTION, item);
+        assert_eq!(KittiesModule::kitties(id(0)), Some(kitty.clone()));
+        assert_eq!(KittiesModule::kitties(id(1)), Some(kitty));
+        assert_eq!(KittiesModule::kitty_owner(id(1)), Some(owner));
+        assert_eq!(KittiesModule::kitty_on_sale(id(1)), Some(()));
+        assert_eq!(KittiesModule::kitty_on_sale(id(0)), None);
+        assert_eq!(KittiesModule::kitty_children(id(0)).into_inner(), vec![id(1)]);
+        assert_eq!(KittiesModule::offers(id(1), bidder), Some(offer));
+        assert_eq!(KittiesModule::offer_count(id(1)), 1);
+        assert_eq!(KittiesModule::leases(id(0)), Some(lease));
+        assert_eq!(KittiesModule::kitty_rating(id(1)), 4);
+        assert_eq!(KittiesModule::next_item_id(DEFAULT_COLLECTION), 2);
+        assert_eq!(KittiesModule::price_bounds(DEFAULT_COLLECTION), Some((1_000, 9_000)));
+        assert_eq!(KittiesModule::total_supply(), 2);
+        assert_eq!(KittiesModule::on_chain_storage_version(), 3);
+    });
+}
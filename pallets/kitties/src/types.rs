@@ -0,0 +1,149 @@
+use crate::{CollectionId, Kitty, KittyId, SwapId};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::pallet_prelude::*;
+use scale_info::TypeInfo;
+use sp_std::vec::Vec;
+
+/// Ancestor tree of a kitty, as returned by the runtime API.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct Pedigree {
+	pub kitty_id: KittyId,
+	/// Pedigrees of both parents. Empty for created kitties and past the requested depth.
+	pub parents: Vec<Pedigree>,
+}
+
+/// Who may edit an attribute of a kitty.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, MaxEncodedLen, TypeInfo)]
+pub enum AttributeNamespace {
+	/// Attributes set by the owner of the kitty.
+	Owner,
+	/// Attributes set by the issuer of its collection, read-only for the owner.
+	Issuer,
+}
+
+/// Balance of the shares a fractionalized kitty is split into.
+pub type Shares = u128;
+
+/// Competition stats decoded from the DNA of a kitty.
+#[derive(Clone, Copy, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+pub struct KittyStats {
+	pub strength: u16,
+	pub agility: u16,
+	pub stamina: u16,
+}
+
+impl KittyStats {
+	/// Highest `power` a kitty can have, with every gene at `u8::MAX`.
+	pub const MAX_POWER: u32 = 16 * u8::MAX as u32;
+
+	/// Sum of all stats, the base score of the kitty in a competition.
+	pub fn power(&self) -> u32 {
+		self.strength as u32 + self.agility as u32 + self.stamina as u32
+	}
+}
+
+impl Kitty {
+	/// Strength, agility and stamina are the sums of the first five, the next five and the last
+	/// six bytes of the DNA.
+	pub fn stats(&self) -> KittyStats {
+		let sum = |genes: &[u8]| genes.iter().map(|gene| *gene as u16).sum();
+		KittyStats {
+			strength: sum(&self.dna[..5]),
+			agility: sum(&self.dna[5..10]),
+			stamina: sum(&self.dna[10..]),
+		}
+	}
+}
+
+/// Lifecycle notifications of kitties, for other pallets to react to. Implemented by `()` as a
+/// no-op and by tuples of handlers.
+///
+/// One action may notify several hooks: a sale calls `on_transferred` for the change of owner,
+/// then `on_sold` for the payment, so handlers counting transfers should not count sales again.
+#[impl_trait_for_tuples::impl_for_tuples(30)]
+pub trait OnKittyEvent<AccountId, Balance> {
+	/// A kitty was created for `owner`, with `create` or `create_soulbound`.
+	fn on_created(_owner: &AccountId, _kitty_id: KittyId) {}
+
+	/// `owner` bred a kitty from `parents`.
+	fn on_bred(_owner: &AccountId, _kitty_id: KittyId, _parents: (KittyId, KittyId)) {}
+
+	/// A kitty changed owner, by any means: transfer, sale, swap, fractionalization or
+	/// redemption. Called once the new owner is stored.
+	fn on_transferred(_from: &AccountId, _to: &AccountId, _kitty_id: KittyId) {}
+
+	/// `buyer` bought a kitty from `seller` for `price`, with `buy` or `accept_offer`. Follows
+	/// `on_transferred` for the same kitty.
+	fn on_sold(_seller: &AccountId, _buyer: &AccountId, _kitty_id: KittyId, _price: Balance) {}
+
+	/// `proposer` and `counterparty` exchanged the `offered` and `wanted` kitties, with `top_up`
+	/// paid by the proposer. Follows `on_transferred` for each kitty.
+	fn on_swapped(
+		_proposer: &AccountId,
+		_counterparty: &AccountId,
+		_offered: &[KittyId],
+		_wanted: &[KittyId],
+		_top_up: Balance,
+	) {
+	}
+
+	/// `proposer` offered swap `swap_id` to `counterparty`.
+	fn on_swap_proposed(_proposer: &AccountId, _counterparty: &AccountId, _swap_id: SwapId) {}
+
+	/// Swap `swap_id` between `proposer` and `counterparty` was cancelled and its top-up
+	/// released.
+	fn on_swap_cancelled(_proposer: &AccountId, _counterparty: &AccountId, _swap_id: SwapId) {}
+
+	/// `owner` put a kitty on sale, with `sale` or `sale_batch`.
+	fn on_listed(_owner: &AccountId, _kitty_id: KittyId) {}
+
+	/// `bidder` made or replaced an offer of `amount` on a kitty.
+	fn on_offer_made(_bidder: &AccountId, _kitty_id: KittyId, _amount: Balance) {}
+
+	/// The offer of `bidder` on a kitty was withdrawn, expired or lapsed because the kitty changed
+	/// owner, and `amount` was released. Not called for accepted offers, see `on_sold`.
+	fn on_offer_removed(_bidder: &AccountId, _kitty_id: KittyId, _amount: Balance) {}
+
+	/// `lessor` proposed to lease a kitty to `lessee`.
+	fn on_lease_offered(_lessor: &AccountId, _lessee: &AccountId, _kitty_id: KittyId) {}
+
+	/// `lessee` accepted the lease of a kitty proposed by `lessor`.
+	fn on_leased(_lessor: &AccountId, _lessee: &AccountId, _kitty_id: KittyId) {}
+
+	/// The lease of a kitty to `lessee` expired.
+	fn on_lease_ended(_lessee: &AccountId, _kitty_id: KittyId) {}
+
+	/// `owner` locked a kitty in its vault for `shares` shares. Follows `on_transferred`.
+	fn on_fractionalized(_owner: &AccountId, _kitty_id: KittyId, _shares: Shares) {}
+
+	/// `owner` redeemed every share of a kitty and took it out of its vault. Follows
+	/// `on_transferred`.
+	fn on_redeemed(_owner: &AccountId, _kitty_id: KittyId) {}
+
+	/// `from` sent `amount` shares of a fractionalized kitty to `to`.
+	fn on_shares_transferred(
+		_from: &AccountId,
+		_to: &AccountId,
+		_kitty_id: KittyId,
+		_amount: Shares,
+	) {
+	}
+
+	/// `who` entered a kitty into the next competition.
+	fn on_entered(_who: &AccountId, _kitty_id: KittyId) {}
+
+	/// A kitty won a competition and `prize` for `winner`. `rating` is its new rating.
+	fn on_competition_won(_winner: &AccountId, _kitty_id: KittyId, _prize: Balance, _rating: u32) {}
+
+	/// The attribute `key` of a kitty was set in `namespace`.
+	fn on_attribute_set(_kitty_id: KittyId, _namespace: AttributeNamespace, _key: &[u8]) {}
+
+	/// The attribute `key` of a kitty was removed from `namespace`.
+	fn on_attribute_cleared(_kitty_id: KittyId, _namespace: AttributeNamespace, _key: &[u8]) {}
+
+	/// A collection was created, by `admin` or by root if `None`.
+	fn on_collection_created(_collection_id: CollectionId, _admin: Option<&AccountId>) {}
+
+	/// The settings of a collection were changed.
+	fn on_collection_updated(_collection_id: CollectionId) {}
+}
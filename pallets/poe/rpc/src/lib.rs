@@ -0,0 +1,110 @@
+//! RPC interface for the poe pallet.
+
+use std::sync::Arc;
+
+use codec::Codec;
+use jsonrpsee::{
+	core::{async_trait, RpcResult},
+	proc_macros::rpc,
+	types::error::{CallError, ErrorObject},
+};
+use pallet_poe_runtime_api::ClaimDetails;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::Bytes;
+use sp_runtime::traits::Block as BlockT;
+
+pub use pallet_poe_runtime_api::PoeApi as PoeRuntimeApi;
+
+#[rpc(client, server)]
+pub trait PoeApi<BlockHash, AccountId, BlockNumber, Hash> {
+	/// Owner, creation block and metadata of a claim.
+	#[method(name = "poe_claim")]
+	fn claim(
+		&self,
+		claim: Bytes,
+		at: Option<BlockHash>,
+	) -> RpcResult<Option<ClaimDetails<AccountId, BlockNumber>>>;
+
+	/// Check that a document hash is part of a batch claim.
+	#[method(name = "poe_verifyMerkleInclusion")]
+	fn verify_merkle_inclusion(
+		&self,
+		root: Hash,
+		leaf: Hash,
+		index: u32,
+		proof: Vec<Hash>,
+		at: Option<BlockHash>,
+	) -> RpcResult<bool>;
+}
+
+/// Provides RPC methods to query the poe pallet.
+pub struct Poe<C, P> {
+	client: Arc<C>,
+	_marker: std::marker::PhantomData<P>,
+}
+
+impl<C, P> Poe<C, P> {
+	/// Create new `Poe` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self { client, _marker: Default::default() }
+	}
+}
+
+/// Error type of this RPC api.
+pub enum Error {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<Error> for i32 {
+	fn from(e: Error) -> i32 {
+		match e {
+			Error::RuntimeError => 1,
+		}
+	}
+}
+
+fn runtime_error(message: &'static str, e: impl ToString) -> jsonrpsee::core::Error {
+	CallError::Custom(ErrorObject::owned(Error::RuntimeError.into(), message, Some(e.to_string())))
+		.into()
+}
+
+#[async_trait]
+impl<C, Block, AccountId, BlockNumber, Hash>
+	PoeApiServer<<Block as BlockT>::Hash, AccountId, BlockNumber, Hash> for Poe<C, Block>
+where
+	Block: BlockT,
+	C: ProvideRuntimeApi<Block> + HeaderBackend<Block> + Send + Sync + 'static,
+	C::Api: PoeRuntimeApi<Block, AccountId, BlockNumber, Hash>,
+	AccountId: Codec + Send + Sync + 'static,
+	BlockNumber: Codec + Send + Sync + 'static,
+	Hash: Codec + Send + Sync + 'static,
+{
+	fn claim(
+		&self,
+		claim: Bytes,
+		at: Option<Block::Hash>,
+	) -> RpcResult<Option<ClaimDetails<AccountId, BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.claim(at_hash, claim.to_vec())
+			.map_err(|e| runtime_error("Unable to query claim.", e))
+	}
+
+	fn verify_merkle_inclusion(
+		&self,
+		root: Hash,
+		leaf: Hash,
+		index: u32,
+		proof: Vec<Hash>,
+		at: Option<Block::Hash>,
+	) -> RpcResult<bool> {
+		let api = self.client.runtime_api();
+		let at_hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		api.verify_merkle_inclusion(at_hash, root, leaf, index, proof)
+			.map_err(|e| runtime_error("Unable to verify Merkle inclusion.", e))
+	}
+}
@@ -0,0 +1,23 @@
+//! Runtime API definition for the poe pallet.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+
+use codec::Codec;
+use sp_std::vec::Vec;
+
+pub use pallet_poe::ClaimDetails;
+
+sp_api::decl_runtime_apis! {
+	pub trait PoeApi<AccountId, BlockNumber, Hash>
+	where
+		AccountId: Codec,
+		BlockNumber: Codec,
+		Hash: Codec,
+	{
+		/// Owner, creation block and metadata of `claim`, if it exists.
+		fn claim(claim: Vec<u8>) -> Option<ClaimDetails<AccountId, BlockNumber>>;
+
+		/// Check that `leaf` sits at position `index` of the batch stored under `root`.
+		fn verify_merkle_inclusion(root: Hash, leaf: Hash, index: u32, proof: Vec<Hash>) -> bool;
+	}
+}
@@ -2,6 +2,13 @@
 
 pub use pallet::*;
 
+pub mod merkle;
+
+mod migrations;
+
+mod types;
+pub use types::*;
+
 #[cfg(test)]
 mod mock;
 
@@ -10,8 +17,18 @@ mod tests;
 
 #[frame_support::pallet]
 pub mod pallet {
-	use frame_support::pallet_prelude::*;
+	use super::*;
+	use crate::migrations::{upgrade_storage, STORAGE_VERSION};
+	use frame_support::{
+		pallet_prelude::*,
+		traits::{Currency, ExistenceRequirement, ReservableCurrency},
+	};
 	use frame_system::pallet_prelude::*;
+	use sp_runtime::traits::{Hash, IdentifyAccount, Saturating, Verify, Zero};
+	use sp_std::vec::Vec;
+
+	pub type BalanceOf<T> =
+		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
 
 	#[pallet::config]
 	pub trait Config: frame_system::Config {
@@ -19,9 +36,52 @@ pub mod pallet {
 		#[pallet::constant]
 		type MaxClaimLength: Get<u32>;
 		type RuntimeEvent: From<Event<Self>> + IsType<<Self as frame_system::Config>::RuntimeEvent>;
+		/// Currency used to reserve metadata deposits.
+		type Currency: ReservableCurrency<Self::AccountId>;
+		/// The base deposit reserved for attaching metadata to a claim.
+		#[pallet::constant]
+		type MetadataDepositBase: Get<BalanceOf<Self>>;
+		/// The additional deposit reserved per byte of encoded metadata.
+		#[pallet::constant]
+		type MetadataDepositPerByte: Get<BalanceOf<Self>>;
+		/// The deposit reserved for a batch claim until it is revoked.
+		#[pallet::constant]
+		type MerkleClaimDeposit: Get<BalanceOf<Self>>;
+		/// The deposit reserved for a co-signed claim until it is finalized or discarded.
+		#[pallet::constant]
+		type PendingClaimDeposit: Get<BalanceOf<Self>>;
+		/// The deposit reserved for each delegation until it is removed.
+		#[pallet::constant]
+		type DelegationDeposit: Get<BalanceOf<Self>>;
+		/// The maximum length of a metadata MIME type.
+		#[pallet::constant]
+		type MaxMimeTypeLength: Get<u32>;
+		/// The maximum length of a metadata URI.
+		#[pallet::constant]
+		type MaxUriLength: Get<u32>;
+		/// The maximum length of a metadata description.
+		#[pallet::constant]
+		type MaxDescriptionLength: Get<u32>;
+		/// The maximum number of previous owners remembered per claim. Older entries are
+		/// dropped first.
+		#[pallet::constant]
+		type MaxOwnershipHistory: Get<u32>;
+		/// The maximum number of co-signers a claim can require.
+		#[pallet::constant]
+		type MaxCoSigners: Get<u32>;
+		/// Number of blocks co-signers have to attest a claim before it can be discarded.
+		#[pallet::constant]
+		type PendingClaimTimeout: Get<Self::BlockNumber>;
+		/// Signature type accepted by `create_claim_signed`.
+		type OffchainSignature: Verify<Signer = Self::OffchainPublic> + Parameter;
+		/// Public key behind `OffchainSignature`, identifying the claim owner.
+		type OffchainPublic: IdentifyAccount<AccountId = Self::AccountId>;
+		/// Origin allowed to remove claims and to manage the freeze list.
+		type ForceOrigin: EnsureOrigin<Self::RuntimeOrigin>;
 	}
 
 	#[pallet::pallet]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
 	#[pallet::storage]
@@ -30,7 +90,74 @@ pub mod pallet {
 		_,
 		Blake2_128Concat,
 		BoundedVec<u8, T::MaxClaimLength>,
-		(T::AccountId, T::BlockNumber),
+		ClaimInfo<T::AccountId, T::BlockNumber>,
+	>;
+
+	/// Co-signed claims that have not reached their threshold yet.
+	#[pallet::storage]
+	#[pallet::getter(fn pending_claims)]
+	pub type PendingClaims<T: Config> =
+		StorageMap<_, Blake2_128Concat, BoundedVec<u8, T::MaxClaimLength>, PendingClaim<T>>;
+
+	/// Accounts allowed to revoke or transfer claims on behalf of their owner, keyed by
+	/// `(owner, claim, delegate)`. A `None` claim covers all claims of the owner. The value is
+	/// the last block in which the delegation is valid, if it expires, and the deposit reserved
+	/// from the owner.
+	#[pallet::storage]
+	#[pallet::getter(fn delegations)]
+	pub type Delegations<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Blake2_128Concat, T::AccountId>,
+			NMapKey<Blake2_128Concat, Option<BoundedVec<u8, T::MaxClaimLength>>>,
+			NMapKey<Blake2_128Concat, T::AccountId>,
+		),
+		(Option<T::BlockNumber>, BalanceOf<T>),
+	>;
+
+	/// Claims that cannot be created or finalized, set by `ForceOrigin`. Keyed by the
+	/// `T::Hashing` of the claim bytes so that moderating a claim does not copy its content.
+	#[pallet::storage]
+	#[pallet::getter(fn frozen_claims)]
+	pub type FrozenClaims<T: Config> = StorageMap<_, Identity, T::Hash, ()>;
+
+	/// Next nonce expected in a signed claim of each account.
+	#[pallet::storage]
+	#[pallet::getter(fn claim_nonce)]
+	pub type ClaimNonces<T: Config> =
+		StorageMap<_, Blake2_128Concat, T::AccountId, u64, ValueQuery>;
+
+	/// Previous owners of a claim, oldest first, with the block each of them became owner.
+	#[pallet::storage]
+	#[pallet::getter(fn ownership_history)]
+	pub type OwnershipHistory<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxClaimLength>,
+		BoundedVec<(T::AccountId, T::BlockNumber), T::MaxOwnershipHistory>,
+		ValueQuery,
+	>;
+
+	/// Optional metadata of a claim, together with the deposit reserved from its owner.
+	#[pallet::storage]
+	#[pallet::getter(fn claim_metadata)]
+	pub type Metadata<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		BoundedVec<u8, T::MaxClaimLength>,
+		(ClaimMetadata<T>, BalanceOf<T>),
+	>;
+
+	/// Batch claims, keyed by the Merkle root over the notarised document hashes.
+	/// Stores the owner, the number of leaves, the block the batch was recorded in and the
+	/// deposit reserved from the owner.
+	#[pallet::storage]
+	#[pallet::getter(fn merkle_claims)]
+	pub type MerkleClaims<T: Config> = StorageMap<
+		_,
+		Blake2_128Concat,
+		T::Hash,
+		(T::AccountId, u32, T::BlockNumber, BalanceOf<T>),
 	>;
 
 	#[pallet::event]
@@ -39,6 +166,37 @@ pub mod pallet {
 		ClaimCreated(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
 		ClaimRevoked(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
 		ClaimTransferred(T::AccountId, T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// A batch of documents was notarised. [owner, root, leaf_count]
+		MerkleClaimCreated(T::AccountId, T::Hash, u32),
+		/// A batch claim was revoked by its owner. [owner, root]
+		MerkleClaimRevoked(T::AccountId, T::Hash),
+		/// Metadata of a claim was set or updated. [owner, claim, deposit]
+		ClaimMetadataSet(T::AccountId, BoundedVec<u8, T::MaxClaimLength>, BalanceOf<T>),
+		/// Metadata of a claim was removed. [owner, claim]
+		ClaimMetadataCleared(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// A claim was submitted for co-signing. [creator, claim, threshold, expires_at]
+		CoSignedClaimCreated(T::AccountId, BoundedVec<u8, T::MaxClaimLength>, u32, T::BlockNumber),
+		/// A co-signer attested a pending claim. [cosigner, claim]
+		ClaimAttested(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// A pending claim reached its threshold and is now recorded. [creator, claim]
+		ClaimFinalized(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// An expired pending claim was discarded. [creator, claim]
+		PendingClaimDiscarded(T::AccountId, BoundedVec<u8, T::MaxClaimLength>),
+		/// An owner authorised a delegate. [owner, delegate, claim, expires_at]
+		DelegateAdded(
+			T::AccountId,
+			T::AccountId,
+			Option<BoundedVec<u8, T::MaxClaimLength>>,
+			Option<T::BlockNumber>,
+		),
+		/// An owner withdrew a delegation. [owner, delegate, claim]
+		DelegateRemoved(T::AccountId, T::AccountId, Option<BoundedVec<u8, T::MaxClaimLength>>),
+		/// A recorded or pending claim was removed by `ForceOrigin`. [owner, claim_hash]
+		ClaimForceRemoved(T::AccountId, T::Hash),
+		/// A claim was added to the freeze list. [claim_hash]
+		ClaimFrozen(T::Hash),
+		/// A claim was removed from the freeze list. [claim_hash]
+		ClaimUnfrozen(T::Hash),
 	}
 
 	#[pallet::error]
@@ -47,10 +205,46 @@ pub mod pallet {
 		ClaimTooLong,
 		ClaimNotExist,
 		NotClaimOwner,
+		/// A batch claim must cover at least one document.
+		EmptyMerkleTree,
+		/// The claim has no metadata attached.
+		MetadataNotExist,
+		/// The threshold must be between one and the number of co-signers.
+		InvalidThreshold,
+		/// The same account is listed more than once as co-signer.
+		DuplicateCoSigner,
+		/// The claim is not waiting for attestations.
+		PendingClaimNotExist,
+		/// The sender is not a co-signer of the claim.
+		NotCoSigner,
+		/// The sender already attested the claim.
+		AlreadyAttested,
+		/// The attestation window of the claim is over.
+		PendingClaimExpired,
+		/// The attestation window of the claim is still open.
+		PendingClaimNotExpired,
+		/// The signature does not match the signer of the claim.
+		WrongSignature,
+		/// The signed claim is past its deadline.
+		SignatureExpired,
+		/// The nonce of the signed claim is not the expected one.
+		InvalidNonce,
+		/// The delegation would already be expired.
+		DelegationExpired,
+		/// No such delegation.
+		DelegationNotExist,
+		/// The claim is on the freeze list.
+		ClaimFrozen,
+		/// The claim is not on the freeze list.
+		ClaimNotFrozen,
 	}
 
 	#[pallet::hooks]
-	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {}
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_runtime_upgrade() -> Weight {
+			upgrade_storage::<T>()
+		}
+	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
@@ -62,14 +256,7 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			ensure!(!Proofs::<T>::contains_key(&claim), Error::<T>::ProofAlreadyExist);
-
-			Proofs::<T>::insert(
-				&claim,
-				(sender.clone(), frame_system::Pallet::<T>::block_number()),
-			);
-
-			Self::deposit_event(Event::ClaimCreated(sender, claim));
+			Self::do_create_claim(sender, claim)?;
 
 			Ok(().into())
 		}
@@ -82,10 +269,16 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			let ClaimInfo { owner, .. } =
+				Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(Self::can_manage(&sender, &owner, &claim), Error::<T>::NotClaimOwner);
 
 			Proofs::<T>::remove(&claim);
+			OwnershipHistory::<T>::remove(&claim);
+			Self::clear_claim_delegations(&owner, &claim);
+			if let Some((_, deposit)) = Metadata::<T>::take(&claim) {
+				T::Currency::unreserve(&owner, deposit);
+			}
 
 			Self::deposit_event(Event::ClaimRevoked(sender, claim));
 
@@ -101,18 +294,503 @@ pub mod pallet {
 		) -> DispatchResultWithPostInfo {
 			let sender = ensure_signed(origin)?;
 
-			//Verify that the sender owns this claim (returns an error if not)
-			let (owner, _) = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
-			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			//Verify that the sender owns this claim or acts for its owner (returns an error if
+			// not)
+			let info = Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			let owner = info.owner.clone();
+			ensure!(Self::can_manage(&sender, &owner, &claim), Error::<T>::NotClaimOwner);
+			// Delegations of the previous owner must not carry over to the new one.
+			Self::clear_claim_delegations(&owner, &claim);
 
+			// The metadata deposit follows the claim to its new owner, who may not have an account
+			// yet. Only the part still reserved from the owner can move.
+			if let Some((metadata, deposit)) = Metadata::<T>::get(&claim) {
+				let missing = T::Currency::unreserve(&owner, deposit);
+				let deposit = deposit.saturating_sub(missing);
+				T::Currency::transfer(
+					&owner,
+					&receiver,
+					deposit,
+					ExistenceRequirement::AllowDeath,
+				)?;
+				T::Currency::reserve(&receiver, deposit)?;
+				Metadata::<T>::insert(&claim, (metadata, deposit));
+			}
+
+			OwnershipHistory::<T>::mutate(&claim, |history| {
+				if history.is_full() {
+					history.remove(0);
+				}
+				let _ = history.try_push((info.owner, info.owned_since));
+			});
+			// `created_at` is kept as is: a transfer must never weaken the existence proof.
 			Proofs::<T>::insert(
 				&claim,
-				(receiver.clone(), frame_system::Pallet::<T>::block_number()),
+				ClaimInfo {
+					owner: receiver.clone(),
+					created_at: info.created_at,
+					owned_since: frame_system::Pallet::<T>::block_number(),
+				},
 			);
 
 			Self::deposit_event(Event::ClaimTransferred(sender, receiver, claim));
 
 			Ok(().into())
 		}
+
+		/// Notarise `leaf_count` documents at once by recording the Merkle root over their
+		/// hashes. Inclusion of a single document is checked with `verify_merkle_inclusion`.
+		#[pallet::call_index(3)]
+		#[pallet::weight(0)]
+		pub fn create_merkle_claim(
+			origin: OriginFor<T>,
+			root: T::Hash,
+			leaf_count: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(leaf_count > 0, Error::<T>::EmptyMerkleTree);
+			ensure!(!MerkleClaims::<T>::contains_key(root), Error::<T>::ProofAlreadyExist);
+
+			let deposit = T::MerkleClaimDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+			MerkleClaims::<T>::insert(
+				root,
+				(sender.clone(), leaf_count, frame_system::Pallet::<T>::block_number(), deposit),
+			);
+
+			Self::deposit_event(Event::MerkleClaimCreated(sender, root, leaf_count));
+
+			Ok(().into())
+		}
+
+		/// Attach metadata to a claim, or replace the existing one. The deposit is adjusted to
+		/// the size of the new record.
+		#[pallet::call_index(4)]
+		#[pallet::weight(0)]
+		pub fn set_claim_metadata(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			metadata: ClaimMetadata<T>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let ClaimInfo { owner, .. } =
+				Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			let old_deposit = Metadata::<T>::get(&claim).map(|(_, d)| d).unwrap_or_else(Zero::zero);
+			let deposit = Self::metadata_deposit(&metadata);
+			if deposit > old_deposit {
+				T::Currency::reserve(&sender, deposit.saturating_sub(old_deposit))?;
+			} else {
+				T::Currency::unreserve(&sender, old_deposit.saturating_sub(deposit));
+			}
+
+			Metadata::<T>::insert(&claim, (metadata, deposit));
+
+			Self::deposit_event(Event::ClaimMetadataSet(sender, claim, deposit));
+
+			Ok(().into())
+		}
+
+		/// Remove the metadata of a claim and release its deposit.
+		#[pallet::call_index(5)]
+		#[pallet::weight(0)]
+		pub fn clear_claim_metadata(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let ClaimInfo { owner, .. } =
+				Proofs::<T>::get(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			let (_, deposit) = Metadata::<T>::take(&claim).ok_or(Error::<T>::MetadataNotExist)?;
+			T::Currency::unreserve(&sender, deposit);
+
+			Self::deposit_event(Event::ClaimMetadataCleared(sender, claim));
+
+			Ok(().into())
+		}
+
+		/// Submit a claim that is only recorded once `threshold` of `cosigners` attested it with
+		/// `attest_claim`. The sender becomes the owner of the finalized claim.
+		#[pallet::call_index(6)]
+		#[pallet::weight(0)]
+		pub fn create_cosigned_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+			cosigners: BoundedVec<T::AccountId, T::MaxCoSigners>,
+			threshold: u32,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			ensure!(!Self::claim_exists(&claim), Error::<T>::ProofAlreadyExist);
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
+			ensure!(
+				threshold > 0 && threshold as usize <= cosigners.len(),
+				Error::<T>::InvalidThreshold
+			);
+			let mut unique = cosigners.to_vec();
+			unique.sort();
+			unique.dedup();
+			ensure!(unique.len() == cosigners.len(), Error::<T>::DuplicateCoSigner);
+
+			let deposit = T::PendingClaimDeposit::get();
+			T::Currency::reserve(&sender, deposit)?;
+			let now = frame_system::Pallet::<T>::block_number();
+			let expires_at = now.saturating_add(T::PendingClaimTimeout::get());
+			PendingClaims::<T>::insert(
+				&claim,
+				PendingClaim {
+					creator: sender.clone(),
+					cosigners,
+					attestations: Default::default(),
+					threshold,
+					submitted_at: now,
+					expires_at,
+					deposit,
+				},
+			);
+
+			Self::deposit_event(Event::CoSignedClaimCreated(sender, claim, threshold, expires_at));
+
+			Ok(().into())
+		}
+
+		/// Attest a pending claim as one of its co-signers. The attestation that reaches the
+		/// threshold finalizes the claim.
+		#[pallet::call_index(7)]
+		#[pallet::weight(0)]
+		pub fn attest_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let mut pending =
+				PendingClaims::<T>::get(&claim).ok_or(Error::<T>::PendingClaimNotExist)?;
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
+			let now = frame_system::Pallet::<T>::block_number();
+			ensure!(now <= pending.expires_at, Error::<T>::PendingClaimExpired);
+			ensure!(pending.cosigners.contains(&sender), Error::<T>::NotCoSigner);
+			ensure!(!pending.attestations.contains(&sender), Error::<T>::AlreadyAttested);
+
+			// Cannot overflow: attestations are a subset of the co-signers.
+			pending
+				.attestations
+				.try_push(sender.clone())
+				.map_err(|_| Error::<T>::NotCoSigner)?;
+			Self::deposit_event(Event::ClaimAttested(sender, claim.clone()));
+
+			if pending.attestations.len() as u32 >= pending.threshold {
+				PendingClaims::<T>::remove(&claim);
+				T::Currency::unreserve(&pending.creator, pending.deposit);
+				// The document provably existed when the claim was submitted.
+				Proofs::<T>::insert(
+					&claim,
+					ClaimInfo {
+						owner: pending.creator.clone(),
+						created_at: pending.submitted_at,
+						owned_since: now,
+					},
+				);
+				Self::deposit_event(Event::ClaimFinalized(pending.creator, claim));
+			} else {
+				PendingClaims::<T>::insert(&claim, pending);
+			}
+
+			Ok(().into())
+		}
+
+		/// Drop a pending claim whose attestation window is over. Anyone can call this.
+		#[pallet::call_index(8)]
+		#[pallet::weight(0)]
+		pub fn discard_expired_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let pending =
+				PendingClaims::<T>::get(&claim).ok_or(Error::<T>::PendingClaimNotExist)?;
+			ensure!(
+				frame_system::Pallet::<T>::block_number() > pending.expires_at,
+				Error::<T>::PendingClaimNotExpired
+			);
+
+			PendingClaims::<T>::remove(&claim);
+			T::Currency::unreserve(&pending.creator, pending.deposit);
+
+			Self::deposit_event(Event::PendingClaimDiscarded(pending.creator, claim));
+
+			Ok(().into())
+		}
+
+		/// Record a claim on behalf of `payload.signer`, who signed `signing_payload(payload)`
+		/// off-chain. The sender only relays the claim and pays the fees; the signer becomes the
+		/// owner.
+		#[pallet::call_index(9)]
+		#[pallet::weight(0)]
+		pub fn create_claim_signed(
+			origin: OriginFor<T>,
+			payload: PreSignedClaim<T>,
+			signature: T::OffchainSignature,
+		) -> DispatchResultWithPostInfo {
+			ensure_signed(origin)?;
+
+			let msg = Self::signing_payload(&payload);
+			ensure!(signature.verify(msg.as_slice(), &payload.signer), Error::<T>::WrongSignature);
+			ensure!(
+				frame_system::Pallet::<T>::block_number() <= payload.deadline,
+				Error::<T>::SignatureExpired
+			);
+			let PreSignedClaim { claim, signer, nonce, .. } = payload;
+			ensure!(nonce == ClaimNonces::<T>::get(&signer), Error::<T>::InvalidNonce);
+
+			ClaimNonces::<T>::insert(&signer, nonce.saturating_add(1));
+			Self::do_create_claim(signer, claim)?;
+
+			Ok(().into())
+		}
+
+		/// Allow `delegate` to revoke or transfer `claim`, or all claims of the sender if `claim`
+		/// is `None`, until `expires_at` if given. Calling it again replaces the expiry.
+		#[pallet::call_index(10)]
+		#[pallet::weight(0)]
+		pub fn add_delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			claim: Option<BoundedVec<u8, T::MaxClaimLength>>,
+			expires_at: Option<T::BlockNumber>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			if let Some(claim) = &claim {
+				let ClaimInfo { owner, .. } =
+					Proofs::<T>::get(claim).ok_or(Error::<T>::ClaimNotExist)?;
+				ensure!(owner == sender, Error::<T>::NotClaimOwner);
+			}
+			if let Some(expires_at) = expires_at {
+				ensure!(
+					frame_system::Pallet::<T>::block_number() <= expires_at,
+					Error::<T>::DelegationExpired
+				);
+			}
+
+			// Replacing the expiry of an existing delegation keeps its deposit.
+			let deposit = match Delegations::<T>::get((&sender, &claim, &delegate)) {
+				Some((_, deposit)) => deposit,
+				None => {
+					let deposit = T::DelegationDeposit::get();
+					T::Currency::reserve(&sender, deposit)?;
+					deposit
+				},
+			};
+			Delegations::<T>::insert((&sender, &claim, &delegate), (expires_at, deposit));
+
+			Self::deposit_event(Event::DelegateAdded(sender, delegate, claim, expires_at));
+
+			Ok(().into())
+		}
+
+		/// Withdraw a delegation previously granted with `add_delegate`.
+		#[pallet::call_index(11)]
+		#[pallet::weight(0)]
+		pub fn remove_delegate(
+			origin: OriginFor<T>,
+			delegate: T::AccountId,
+			claim: Option<BoundedVec<u8, T::MaxClaimLength>>,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (_, deposit) = Delegations::<T>::take((&sender, &claim, &delegate))
+				.ok_or(Error::<T>::DelegationNotExist)?;
+			T::Currency::unreserve(&sender, deposit);
+
+			Self::deposit_event(Event::DelegateRemoved(sender, delegate, claim));
+
+			Ok(().into())
+		}
+
+		/// Remove a recorded or pending claim regardless of its owner, e.g. because its content
+		/// is unlawful. Use `freeze_claim` to keep it from being submitted again.
+		#[pallet::call_index(12)]
+		#[pallet::weight(0)]
+		pub fn force_remove_claim(
+			origin: OriginFor<T>,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			let owner = if let Some(ClaimInfo { owner, .. }) = Proofs::<T>::take(&claim) {
+				OwnershipHistory::<T>::remove(&claim);
+				Self::clear_claim_delegations(&owner, &claim);
+				if let Some((_, deposit)) = Metadata::<T>::take(&claim) {
+					T::Currency::unreserve(&owner, deposit);
+				}
+				owner
+			} else {
+				let pending = PendingClaims::<T>::take(&claim).ok_or(Error::<T>::ClaimNotExist)?;
+				T::Currency::unreserve(&pending.creator, pending.deposit);
+				pending.creator
+			};
+
+			Self::deposit_event(Event::ClaimForceRemoved(owner, T::Hashing::hash(&claim)));
+
+			Ok(().into())
+		}
+
+		/// Prevent the claim with hash `claim_hash` from being created or finalized. Existing
+		/// claims are not affected.
+		#[pallet::call_index(13)]
+		#[pallet::weight(0)]
+		pub fn freeze_claim(
+			origin: OriginFor<T>,
+			claim_hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			ensure!(!FrozenClaims::<T>::contains_key(claim_hash), Error::<T>::ClaimFrozen);
+			FrozenClaims::<T>::insert(claim_hash, ());
+
+			Self::deposit_event(Event::ClaimFrozen(claim_hash));
+
+			Ok(().into())
+		}
+
+		/// Take the claim with hash `claim_hash` off the freeze list.
+		#[pallet::call_index(14)]
+		#[pallet::weight(0)]
+		pub fn unfreeze_claim(
+			origin: OriginFor<T>,
+			claim_hash: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			T::ForceOrigin::ensure_origin(origin)?;
+
+			ensure!(FrozenClaims::<T>::contains_key(claim_hash), Error::<T>::ClaimNotFrozen);
+			FrozenClaims::<T>::remove(claim_hash);
+
+			Self::deposit_event(Event::ClaimUnfrozen(claim_hash));
+
+			Ok(().into())
+		}
+
+		/// Remove a batch claim and release its deposit.
+		#[pallet::call_index(15)]
+		#[pallet::weight(0)]
+		pub fn revoke_merkle_claim(
+			origin: OriginFor<T>,
+			root: T::Hash,
+		) -> DispatchResultWithPostInfo {
+			let sender = ensure_signed(origin)?;
+
+			let (owner, _, _, deposit) =
+				MerkleClaims::<T>::get(root).ok_or(Error::<T>::ClaimNotExist)?;
+			ensure!(owner == sender, Error::<T>::NotClaimOwner);
+
+			MerkleClaims::<T>::remove(root);
+			T::Currency::unreserve(&sender, deposit);
+
+			Self::deposit_event(Event::MerkleClaimRevoked(sender, root));
+
+			Ok(().into())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		/// Bytes the signer of a `PreSignedClaim` signs: the payload prefixed with
+		/// `CLAIM_SIGNING_CONTEXT` and the genesis hash, so that signatures are only valid for
+		/// this pallet on this chain.
+		pub fn signing_payload(payload: &PreSignedClaim<T>) -> Vec<u8> {
+			let genesis_hash = frame_system::Pallet::<T>::block_hash(T::BlockNumber::zero());
+			(CLAIM_SIGNING_CONTEXT, genesis_hash, payload).encode()
+		}
+
+		/// Whether `claim` is recorded or waiting for co-signers.
+		fn claim_exists(claim: &BoundedVec<u8, T::MaxClaimLength>) -> bool {
+			Proofs::<T>::contains_key(claim) || PendingClaims::<T>::contains_key(claim)
+		}
+
+		fn do_create_claim(
+			owner: T::AccountId,
+			claim: BoundedVec<u8, T::MaxClaimLength>,
+		) -> DispatchResult {
+			ensure!(!Self::claim_exists(&claim), Error::<T>::ProofAlreadyExist);
+			ensure!(!Self::is_frozen(&claim), Error::<T>::ClaimFrozen);
+
+			let now = frame_system::Pallet::<T>::block_number();
+			Proofs::<T>::insert(
+				&claim,
+				ClaimInfo { owner: owner.clone(), created_at: now, owned_since: now },
+			);
+
+			Self::deposit_event(Event::ClaimCreated(owner, claim));
+
+			Ok(())
+		}
+
+		fn is_frozen(claim: &BoundedVec<u8, T::MaxClaimLength>) -> bool {
+			FrozenClaims::<T>::contains_key(T::Hashing::hash(claim))
+		}
+
+		/// Whether `who` may revoke or transfer `claim` owned by `owner`.
+		fn can_manage(
+			who: &T::AccountId,
+			owner: &T::AccountId,
+			claim: &BoundedVec<u8, T::MaxClaimLength>,
+		) -> bool {
+			if who == owner {
+				return true
+			}
+
+			let now = frame_system::Pallet::<T>::block_number();
+			[Some(claim.clone()), None].into_iter().any(|scope| {
+				matches!(
+					Delegations::<T>::get((owner, scope, who)),
+					Some((expires_at, _)) if expires_at.map_or(true, |expires_at| now <= expires_at)
+				)
+			})
+		}
+
+		/// Remove the delegations `owner` granted for `claim` and release their deposits.
+		fn clear_claim_delegations(
+			owner: &T::AccountId,
+			claim: &BoundedVec<u8, T::MaxClaimLength>,
+		) {
+			let deposit = Delegations::<T>::drain_prefix((owner.clone(), Some(claim.clone())))
+				.map(|(_, (_, deposit))| deposit)
+				.fold(Zero::zero(), |total: BalanceOf<T>, deposit| total.saturating_add(deposit));
+			T::Currency::unreserve(owner, deposit);
+		}
+
+		fn metadata_deposit(metadata: &ClaimMetadata<T>) -> BalanceOf<T> {
+			let bytes: BalanceOf<T> = (metadata.encoded_size() as u32).into();
+			T::MetadataDepositBase::get()
+				.saturating_add(T::MetadataDepositPerByte::get().saturating_mul(bytes))
+		}
+
+		/// Ownership, history and metadata of `claim`, for the runtime API.
+		pub fn claim_details(claim: Vec<u8>) -> Option<ClaimDetails<T::AccountId, T::BlockNumber>> {
+			let claim = BoundedVec::<u8, T::MaxClaimLength>::try_from(claim).ok()?;
+			let ClaimInfo { owner, created_at, owned_since } = Proofs::<T>::get(&claim)?;
+			let previous_owners = OwnershipHistory::<T>::get(&claim).into_inner();
+			let metadata = Metadata::<T>::get(&claim).map(|(metadata, _)| metadata.into());
+			Some(ClaimDetails { owner, created_at, owned_since, previous_owners, metadata })
+		}
+
+		/// Whether `leaf` is the `index`-th document of the batch stored under `root`.
+		pub fn verify_merkle_inclusion(
+			root: T::Hash,
+			leaf: T::Hash,
+			index: u32,
+			proof: Vec<T::Hash>,
+		) -> bool {
+			MerkleClaims::<T>::get(root).map_or(false, |(_, leaf_count, _, _)| {
+				crate::merkle::verify_proof::<T::Hashing>(root, leaf, index, leaf_count, &proof)
+			})
+		}
 	}
 }
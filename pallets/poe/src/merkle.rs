@@ -0,0 +1,78 @@
+//! Binary Merkle tree over document hashes, used by batch claims.
+//!
+//! A parent node is `H::hash_of(&(left, right))`. When a level has an odd number of nodes the
+//! last one is promoted to the next level unchanged, so a proof only carries the siblings that
+//! actually exist. Nothing here touches storage, so clients can build roots and proofs with the
+//! same code the runtime verifies them with.
+
+use sp_runtime::traits::Hash;
+use sp_std::vec::Vec;
+
+fn next_level<H: Hash>(level: &[H::Output]) -> Vec<H::Output> {
+	level
+		.chunks(2)
+		.map(|pair| match pair {
+			[left, right] => H::hash_of(&(left, right)),
+			_ => pair[0],
+		})
+		.collect()
+}
+
+/// Root of the tree built over `leaves`, or `None` if there are no leaves.
+pub fn merkle_root<H: Hash>(leaves: &[H::Output]) -> Option<H::Output> {
+	let mut level = leaves.to_vec();
+	while level.len() > 1 {
+		level = next_level::<H>(&level);
+	}
+	level.pop()
+}
+
+/// Sibling hashes needed to prove the leaf at `index`, ordered from the leaf upwards.
+pub fn merkle_proof<H: Hash>(leaves: &[H::Output], index: u32) -> Option<Vec<H::Output>> {
+	let mut index = index as usize;
+	if index >= leaves.len() {
+		return None
+	}
+
+	let mut proof = Vec::new();
+	let mut level = leaves.to_vec();
+	while level.len() > 1 {
+		if let Some(sibling) = level.get(index ^ 1) {
+			proof.push(*sibling);
+		}
+		level = next_level::<H>(&level);
+		index /= 2;
+	}
+	Some(proof)
+}
+
+/// Check that `leaf` is the `index`-th of `leaf_count` leaves under `root`.
+pub fn verify_proof<H: Hash>(
+	root: H::Output,
+	leaf: H::Output,
+	index: u32,
+	leaf_count: u32,
+	proof: &[H::Output],
+) -> bool {
+	if index >= leaf_count {
+		return false
+	}
+
+	let (mut index, mut width) = (index, leaf_count);
+	let mut siblings = proof.iter();
+	let mut node = leaf;
+	while width > 1 {
+		if index ^ 1 < width {
+			let Some(sibling) = siblings.next() else { return false };
+			node = if index % 2 == 0 {
+				H::hash_of(&(node, sibling))
+			} else {
+				H::hash_of(&(sibling, node))
+			};
+		}
+		index /= 2;
+		width = (width + 1) / 2;
+	}
+
+	siblings.next().is_none() && node == root
+}
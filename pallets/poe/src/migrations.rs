@@ -0,0 +1,39 @@
+use crate::{ClaimInfo, Config, Pallet, Proofs};
+use frame_support::{pallet_prelude::*, traits::GetStorageVersion, weights::Weight};
+
+pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(1);
+
+pub(crate) fn upgrade_storage<T: Config>() -> Weight {
+	let on_chain_ver = Pallet::<T>::on_chain_storage_version();
+	if on_chain_ver == v0::STORAGE_VERSION {
+		let count = from_v0::<T>();
+		STORAGE_VERSION.put::<Pallet<T>>();
+		return T::DbWeight::get().reads_writes(count + 1, count + 1)
+	}
+
+	T::DbWeight::get().reads(1)
+}
+
+mod v0 {
+	use super::*;
+
+	pub(crate) const STORAGE_VERSION: StorageVersion = StorageVersion::new(0);
+
+	/// Owner and the block of the last creation or transfer.
+	pub(crate) type Proof<AccountId, BlockNumber> = (AccountId, BlockNumber);
+}
+
+////////////////////////////////////////////////////////////////////////////////////////////////////
+/// v0 -> current
+
+/// v0 overwrote the block on every transfer, so the original creation block of a claim that has
+/// already changed hands is lost. The best we can do is to start both counters from the stored
+/// block.
+fn from_v0<T: Config>() -> u64 {
+	let mut count = 0;
+	Proofs::<T>::translate::<v0::Proof<T::AccountId, T::BlockNumber>, _>(|_, (owner, block)| {
+		count += 1;
+		Some(ClaimInfo { owner, created_at: block, owned_since: block })
+	});
+	count
+}
@@ -1,6 +1,12 @@
 use super::*;
 use crate::{mock::*, Error};
+use codec::Encode;
 use frame_support::{assert_noop, assert_ok, BoundedVec};
+use sp_core::H256;
+use sp_runtime::{
+	testing::TestSignature,
+	traits::{BlakeTwo256, Hash},
+};
 
 #[test]
 fn create_claim_tests() {
@@ -16,7 +22,11 @@ fn create_claim_tests() {
 
 		assert_eq!(
 			Proofs::<Test>::get(&claim),
-			Some((sender.clone(), frame_system::Pallet::<Test>::block_number())) // Asserts that the proof was added correctly to the storage
+			Some(ClaimInfo {
+				owner: sender.clone(),
+				created_at: frame_system::Pallet::<Test>::block_number(),
+				owned_since: frame_system::Pallet::<Test>::block_number(),
+			}) // Asserts that the proof was added correctly to the storage
 		);
 
 		assert_noop!(
@@ -71,7 +81,11 @@ fn transfer_claim_tests() {
 
 		assert_eq!(
 			Proofs::<Test>::get(&claim),
-			Some((receiver.clone(), frame_system::Pallet::<Test>::block_number())) // Asserts that the claim was transferred correctly
+			Some(ClaimInfo {
+				owner: receiver.clone(),
+				created_at: frame_system::Pallet::<Test>::block_number(),
+				owned_since: frame_system::Pallet::<Test>::block_number(),
+			}) // Asserts that the claim was transferred correctly
 		);
 
 		assert_noop!(
@@ -96,4 +110,552 @@ fn transfer_claim_tests() {
 			Error::<Test>::ClaimNotExist
 		);
 	});
-}
\ No newline at end of file
+}
+
+#[test]
+fn merkle_proofs_tests() {
+	// Odd leaf counts exercise the promotion of the unpaired last node.
+	for leaf_count in 1..=7u32 {
+		let leaves: Vec<H256> = (0..leaf_count).map(|i| BlakeTwo256::hash_of(&i)).collect();
+		let root = merkle::merkle_root::<BlakeTwo256>(&leaves).unwrap();
+
+		for (index, leaf) in leaves.iter().enumerate() {
+			let proof = merkle::merkle_proof::<BlakeTwo256>(&leaves, index as u32).unwrap();
+			assert!(merkle::verify_proof::<BlakeTwo256>(
+				root,
+				*leaf,
+				index as u32,
+				leaf_count,
+				&proof
+			));
+			// The same proof must not validate the leaf at another position.
+			assert!(!merkle::verify_proof::<BlakeTwo256>(
+				root,
+				*leaf,
+				(index as u32 + 1) % (leaf_count + 1),
+				leaf_count,
+				&proof
+			));
+		}
+	}
+
+	assert_eq!(merkle::merkle_root::<BlakeTwo256>(&[]), None);
+	assert_eq!(merkle::merkle_proof::<BlakeTwo256>(&[H256::zero()], 1), None);
+}
+
+#[test]
+fn create_merkle_claim_tests() {
+	new_test_ext().execute_with(|| {
+		let leaves: Vec<H256> = (0..5u32).map(|i| BlakeTwo256::hash_of(&i)).collect();
+		let root = merkle::merkle_root::<BlakeTwo256>(&leaves).unwrap();
+		let sender = 1;
+
+		assert_noop!(
+			PoeModule::create_merkle_claim(RuntimeOrigin::signed(sender), root, 0),
+			Error::<Test>::EmptyMerkleTree
+		);
+
+		// Nothing is verified until the root has been notarised.
+		let proof = merkle::merkle_proof::<BlakeTwo256>(&leaves, 3).unwrap();
+		assert!(!PoeModule::verify_merkle_inclusion(root, leaves[3], 3, proof.clone()));
+
+		assert_ok!(PoeModule::create_merkle_claim(RuntimeOrigin::signed(sender), root, 5));
+		assert_eq!(
+			MerkleClaims::<Test>::get(root),
+			Some((sender, 5, frame_system::Pallet::<Test>::block_number(), 20))
+		);
+		assert_eq!(Balances::reserved_balance(sender), 20);
+
+		assert!(PoeModule::verify_merkle_inclusion(root, leaves[3], 3, proof.clone()));
+		assert!(!PoeModule::verify_merkle_inclusion(root, leaves[2], 3, proof.clone()));
+
+		assert_noop!(
+			PoeModule::create_merkle_claim(RuntimeOrigin::signed(2), root, 5),
+			Error::<Test>::ProofAlreadyExist
+		);
+
+		// Revoking the batch releases its deposit.
+		assert_noop!(
+			PoeModule::revoke_merkle_claim(RuntimeOrigin::signed(2), root),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::revoke_merkle_claim(RuntimeOrigin::signed(sender), root));
+		System::assert_last_event(Event::MerkleClaimRevoked(sender, root).into());
+		assert_eq!(Balances::reserved_balance(sender), 0);
+		assert!(!PoeModule::verify_merkle_inclusion(root, leaves[3], 3, proof));
+		assert_noop!(
+			PoeModule::revoke_merkle_claim(RuntimeOrigin::signed(sender), root),
+			Error::<Test>::ClaimNotExist
+		);
+	});
+}
+
+fn metadata(description: &[u8]) -> ClaimMetadata<Test> {
+	ClaimMetadata {
+		mime_type: BoundedVec::try_from(b"application/pdf".to_vec()).unwrap(),
+		file_size: Some(1024),
+		uri: BoundedVec::try_from(b"ipfs://doc".to_vec()).unwrap(),
+		description: BoundedVec::try_from(description.to_vec()).unwrap(),
+	}
+}
+
+#[test]
+fn claim_metadata_tests() {
+	new_test_ext().execute_with(|| {
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let owner = 1;
+		let receiver = 2;
+
+		assert_noop!(
+			PoeModule::set_claim_metadata(
+				RuntimeOrigin::signed(owner),
+				claim.clone(),
+				metadata(b"")
+			),
+			Error::<Test>::ClaimNotExist
+		);
+
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(owner), claim.clone()));
+
+		assert_noop!(
+			PoeModule::set_claim_metadata(
+				RuntimeOrigin::signed(receiver),
+				claim.clone(),
+				metadata(b"")
+			),
+			Error::<Test>::NotClaimOwner
+		);
+
+		// The deposit grows and shrinks with the size of the record.
+		let long = metadata(b"signed lease agreement");
+		let long_deposit = 10 + long.encoded_size() as u128;
+		assert_ok!(PoeModule::set_claim_metadata(
+			RuntimeOrigin::signed(owner),
+			claim.clone(),
+			long.clone()
+		));
+		assert_eq!(Balances::reserved_balance(owner), long_deposit);
+
+		let short = metadata(b"lease");
+		let short_deposit = 10 + short.encoded_size() as u128;
+		assert_ok!(PoeModule::set_claim_metadata(
+			RuntimeOrigin::signed(owner),
+			claim.clone(),
+			short.clone()
+		));
+		assert_eq!(Balances::reserved_balance(owner), short_deposit);
+		assert_eq!(
+			PoeModule::claim_details(claim.to_vec()),
+			Some(ClaimDetails {
+				owner,
+				created_at: frame_system::Pallet::<Test>::block_number(),
+				owned_since: frame_system::Pallet::<Test>::block_number(),
+				previous_owners: vec![],
+				metadata: Some(short.into()),
+			})
+		);
+
+		// Transferring the claim moves the deposit along with it.
+		assert_ok!(PoeModule::transfer_claim(
+			RuntimeOrigin::signed(owner),
+			claim.clone(),
+			receiver
+		));
+		assert_eq!(Balances::reserved_balance(owner), 0);
+		assert_eq!(Balances::reserved_balance(receiver), short_deposit);
+
+		assert_ok!(PoeModule::clear_claim_metadata(RuntimeOrigin::signed(receiver), claim.clone()));
+		assert_eq!(Balances::reserved_balance(receiver), 0);
+		assert_noop!(
+			PoeModule::clear_claim_metadata(RuntimeOrigin::signed(receiver), claim.clone()),
+			Error::<Test>::MetadataNotExist
+		);
+
+		// Revoking a claim releases its deposit.
+		assert_ok!(PoeModule::set_claim_metadata(
+			RuntimeOrigin::signed(receiver),
+			claim.clone(),
+			long
+		));
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(receiver), claim.clone()));
+		assert_eq!(Balances::reserved_balance(receiver), 0);
+		assert!(!Metadata::<Test>::contains_key(&claim));
+	});
+}
+
+#[test]
+fn transfer_claim_metadata_deposit_tests() {
+	new_test_ext().execute_with(|| {
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let owner = 1;
+		let unfunded = 4;
+		let short = metadata(b"lease");
+		let deposit = 10 + short.encoded_size() as u128;
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(owner), claim.clone()));
+		assert_ok!(PoeModule::set_claim_metadata(
+			RuntimeOrigin::signed(owner),
+			claim.clone(),
+			short.clone()
+		));
+
+		// The receiver has no account yet: the deposit opens it.
+		assert_ok!(PoeModule::transfer_claim(
+			RuntimeOrigin::signed(owner),
+			claim.clone(),
+			unfunded
+		));
+		assert_eq!(Balances::reserved_balance(owner), 0);
+		assert_eq!(Balances::reserved_balance(unfunded), deposit);
+		assert_eq!(Balances::free_balance(unfunded), 0);
+		assert_eq!(PoeModule::claim_metadata(&claim), Some((short.clone(), deposit)));
+
+		// Part of the reserve was released behind the pallet's back: only the rest moves, and
+		// that is what gets recorded.
+		assert_ok!(Balances::force_unreserve(RuntimeOrigin::root(), unfunded, 5));
+		assert_ok!(PoeModule::transfer_claim(
+			RuntimeOrigin::signed(unfunded),
+			claim.clone(),
+			owner
+		));
+		assert_eq!(Balances::reserved_balance(owner), deposit - 5);
+		assert_eq!(PoeModule::claim_metadata(&claim), Some((short, deposit - 5)));
+	});
+}
+
+#[test]
+fn transfer_keeps_creation_block_tests() {
+	new_test_ext().execute_with(|| {
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+
+		System::set_block_number(1);
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(1), claim.clone()));
+
+		// The history is bounded to two entries in the mock; the oldest one is dropped first.
+		for (block, (from, to)) in [(1, 2), (2, 3), (3, 1)].into_iter().enumerate() {
+			System::set_block_number(block as u64 + 5);
+			assert_ok!(PoeModule::transfer_claim(RuntimeOrigin::signed(from), claim.clone(), to));
+		}
+
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner: 1, created_at: 1, owned_since: 7 })
+		);
+		assert_eq!(PoeModule::ownership_history(&claim).into_inner(), vec![(2, 5), (3, 6)]);
+
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(1), claim.clone()));
+		assert!(!OwnershipHistory::<Test>::contains_key(&claim));
+	});
+}
+
+#[test]
+fn cosigned_claim_tests() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let creator = 1;
+		let cosigners = BoundedVec::try_from(vec![2, 3]).unwrap();
+
+		assert_noop!(
+			PoeModule::create_cosigned_claim(
+				RuntimeOrigin::signed(creator),
+				claim.clone(),
+				cosigners.clone(),
+				3
+			),
+			Error::<Test>::InvalidThreshold
+		);
+		assert_noop!(
+			PoeModule::create_cosigned_claim(
+				RuntimeOrigin::signed(creator),
+				claim.clone(),
+				BoundedVec::try_from(vec![2, 2]).unwrap(),
+				2
+			),
+			Error::<Test>::DuplicateCoSigner
+		);
+
+		assert_ok!(PoeModule::create_cosigned_claim(
+			RuntimeOrigin::signed(creator),
+			claim.clone(),
+			cosigners,
+			2
+		));
+		assert_eq!(Balances::reserved_balance(creator), 30);
+		// A pending claim cannot be claimed again by anyone else.
+		assert_noop!(
+			PoeModule::create_claim(RuntimeOrigin::signed(2), claim.clone()),
+			Error::<Test>::ProofAlreadyExist
+		);
+
+		assert_noop!(
+			PoeModule::attest_claim(RuntimeOrigin::signed(creator), claim.clone()),
+			Error::<Test>::NotCoSigner
+		);
+		assert_ok!(PoeModule::attest_claim(RuntimeOrigin::signed(2), claim.clone()));
+		System::assert_last_event(Event::ClaimAttested(2, claim.clone()).into());
+		assert_noop!(
+			PoeModule::attest_claim(RuntimeOrigin::signed(2), claim.clone()),
+			Error::<Test>::AlreadyAttested
+		);
+		assert!(!Proofs::<Test>::contains_key(&claim));
+
+		System::set_block_number(4);
+		assert_ok!(PoeModule::attest_claim(RuntimeOrigin::signed(3), claim.clone()));
+		System::assert_last_event(Event::ClaimFinalized(creator, claim.clone()).into());
+		assert!(!PendingClaims::<Test>::contains_key(&claim));
+		assert_eq!(Balances::reserved_balance(creator), 0);
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner: creator, created_at: 1, owned_since: 4 })
+		);
+	});
+}
+
+#[test]
+fn discard_expired_claim_tests() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let cosigners = BoundedVec::try_from(vec![2, 3]).unwrap();
+
+		assert_ok!(PoeModule::create_cosigned_claim(
+			RuntimeOrigin::signed(1),
+			claim.clone(),
+			cosigners,
+			2
+		));
+		assert_ok!(PoeModule::attest_claim(RuntimeOrigin::signed(2), claim.clone()));
+
+		System::set_block_number(11);
+		assert_noop!(
+			PoeModule::discard_expired_claim(RuntimeOrigin::signed(3), claim.clone()),
+			Error::<Test>::PendingClaimNotExpired
+		);
+
+		System::set_block_number(12);
+		assert_noop!(
+			PoeModule::attest_claim(RuntimeOrigin::signed(3), claim.clone()),
+			Error::<Test>::PendingClaimExpired
+		);
+		assert_ok!(PoeModule::discard_expired_claim(RuntimeOrigin::signed(3), claim.clone()));
+		System::assert_last_event(Event::PendingClaimDiscarded(1, claim.clone()).into());
+		assert!(!PendingClaims::<Test>::contains_key(&claim));
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		// The claim is free to be recorded again.
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(2), claim));
+	});
+}
+
+#[test]
+fn create_claim_signed_tests() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let signer = 1;
+		let relayer = 2;
+		let payload =
+			PreSignedClaim::<Test> { claim: claim.clone(), signer, nonce: 0, deadline: 5 };
+		let signature = TestSignature(signer, PoeModule::signing_payload(&payload));
+
+		assert_noop!(
+			PoeModule::create_claim_signed(
+				RuntimeOrigin::signed(relayer),
+				payload.clone(),
+				TestSignature(relayer, PoeModule::signing_payload(&payload))
+			),
+			Error::<Test>::WrongSignature
+		);
+		// Signatures over the bare payload could be replayed on other chains.
+		assert_noop!(
+			PoeModule::create_claim_signed(
+				RuntimeOrigin::signed(relayer),
+				payload.clone(),
+				TestSignature(signer, payload.encode())
+			),
+			Error::<Test>::WrongSignature
+		);
+
+		assert_ok!(PoeModule::create_claim_signed(
+			RuntimeOrigin::signed(relayer),
+			payload.clone(),
+			signature.clone()
+		));
+		// The signer owns the claim, not the relayer.
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().owner, signer);
+		assert_eq!(PoeModule::claim_nonce(signer), 1);
+
+		// Replaying the same signed payload is rejected even once the claim is gone.
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(signer), claim.clone()));
+		assert_noop!(
+			PoeModule::create_claim_signed(RuntimeOrigin::signed(relayer), payload, signature),
+			Error::<Test>::InvalidNonce
+		);
+
+		let payload = PreSignedClaim::<Test> { claim, signer, nonce: 1, deadline: 5 };
+		System::set_block_number(6);
+		assert_noop!(
+			PoeModule::create_claim_signed(
+				RuntimeOrigin::signed(relayer),
+				payload.clone(),
+				TestSignature(signer, PoeModule::signing_payload(&payload))
+			),
+			Error::<Test>::SignatureExpired
+		);
+	});
+}
+
+#[test]
+fn delegated_claim_management_tests() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let other: BoundedVec<u8, _> = BoundedVec::try_from(vec![2, 3]).unwrap();
+		let org = 1;
+		let staff = 2;
+		let receiver = 3;
+
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(org), claim.clone()));
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(org), other.clone()));
+
+		assert_noop!(
+			PoeModule::add_delegate(RuntimeOrigin::signed(staff), staff, Some(claim.clone()), None),
+			Error::<Test>::NotClaimOwner
+		);
+
+		// A delegation for one claim does not cover the others.
+		assert_ok!(PoeModule::add_delegate(
+			RuntimeOrigin::signed(org),
+			staff,
+			Some(claim.clone()),
+			None
+		));
+		assert_eq!(Balances::reserved_balance(org), 5);
+		assert_noop!(
+			PoeModule::revoke_claim(RuntimeOrigin::signed(staff), other.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_ok!(PoeModule::transfer_claim(
+			RuntimeOrigin::signed(staff),
+			claim.clone(),
+			receiver
+		));
+		assert_eq!(Proofs::<Test>::get(&claim).unwrap().owner, receiver);
+		// The delegation ended with the ownership of the org.
+		assert!(!Delegations::<Test>::contains_key((org, Some(claim.clone()), staff)));
+		assert_eq!(Balances::reserved_balance(org), 0);
+
+		// A delegation over all claims expires with its block.
+		assert_ok!(PoeModule::add_delegate(RuntimeOrigin::signed(org), staff, None, Some(3)));
+		System::set_block_number(4);
+		assert_noop!(
+			PoeModule::revoke_claim(RuntimeOrigin::signed(staff), other.clone()),
+			Error::<Test>::NotClaimOwner
+		);
+		assert_noop!(
+			PoeModule::add_delegate(RuntimeOrigin::signed(org), staff, None, Some(3)),
+			Error::<Test>::DelegationExpired
+		);
+
+		// Renewing the delegation keeps its deposit.
+		assert_ok!(PoeModule::add_delegate(RuntimeOrigin::signed(org), staff, None, None));
+		assert_eq!(Balances::reserved_balance(org), 5);
+		assert_ok!(PoeModule::revoke_claim(RuntimeOrigin::signed(staff), other.clone()));
+		System::assert_last_event(Event::ClaimRevoked(staff, other).into());
+
+		assert_ok!(PoeModule::remove_delegate(RuntimeOrigin::signed(org), staff, None));
+		assert_eq!(Balances::reserved_balance(org), 0);
+		assert_noop!(
+			PoeModule::remove_delegate(RuntimeOrigin::signed(org), staff, None),
+			Error::<Test>::DelegationNotExist
+		);
+	});
+}
+
+#[test]
+fn moderation_tests() {
+	new_test_ext().execute_with(|| {
+		System::set_block_number(1);
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let claim_hash = BlakeTwo256::hash(&claim);
+		let owner = 1;
+
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(owner), claim.clone()));
+		assert_ok!(PoeModule::set_claim_metadata(
+			RuntimeOrigin::signed(owner),
+			claim.clone(),
+			metadata(b"")
+		));
+
+		assert_noop!(
+			PoeModule::force_remove_claim(RuntimeOrigin::signed(owner), claim.clone()),
+			sp_runtime::DispatchError::BadOrigin
+		);
+		assert_ok!(PoeModule::force_remove_claim(RuntimeOrigin::root(), claim.clone()));
+		System::assert_last_event(Event::ClaimForceRemoved(owner, claim_hash).into());
+		assert!(!Proofs::<Test>::contains_key(&claim));
+		assert_eq!(Balances::reserved_balance(owner), 0);
+		assert_noop!(
+			PoeModule::force_remove_claim(RuntimeOrigin::root(), claim.clone()),
+			Error::<Test>::ClaimNotExist
+		);
+
+		// Pending claims can be removed too, releasing the deposit of their creator.
+		assert_ok!(PoeModule::create_cosigned_claim(
+			RuntimeOrigin::signed(owner),
+			claim.clone(),
+			BoundedVec::try_from(vec![2]).unwrap(),
+			1
+		));
+		assert_ok!(PoeModule::force_remove_claim(RuntimeOrigin::root(), claim.clone()));
+		assert!(!PendingClaims::<Test>::contains_key(&claim));
+		assert_eq!(Balances::reserved_balance(owner), 0);
+
+		assert_ok!(PoeModule::freeze_claim(RuntimeOrigin::root(), claim_hash));
+		System::assert_last_event(Event::ClaimFrozen(claim_hash).into());
+		assert_noop!(
+			PoeModule::create_claim(RuntimeOrigin::signed(owner), claim.clone()),
+			Error::<Test>::ClaimFrozen
+		);
+		assert_noop!(
+			PoeModule::create_cosigned_claim(
+				RuntimeOrigin::signed(owner),
+				claim.clone(),
+				BoundedVec::try_from(vec![2]).unwrap(),
+				1
+			),
+			Error::<Test>::ClaimFrozen
+		);
+
+		assert_ok!(PoeModule::unfreeze_claim(RuntimeOrigin::root(), claim_hash));
+		System::assert_last_event(Event::ClaimUnfrozen(claim_hash).into());
+		assert_noop!(
+			PoeModule::unfreeze_claim(RuntimeOrigin::root(), claim_hash),
+			Error::<Test>::ClaimNotFrozen
+		);
+		assert_ok!(PoeModule::create_claim(RuntimeOrigin::signed(owner), claim));
+	});
+}
+
+#[test]
+fn migrates_v0_proofs() {
+	use frame_support::traits::{GetStorageVersion, StorageVersion};
+
+	new_test_ext().execute_with(|| {
+		let claim: BoundedVec<u8, _> = BoundedVec::try_from(vec![0, 1]).unwrap();
+		let owner: u64 = 1;
+		let block: u64 = 5;
+
+		// v0 stored the owner and the block of the last creation or transfer.
+		StorageVersion::new(0).put::<PoeModule>();
+		sp_io::storage::set(&Proofs::<Test>::hashed_key_for(&claim), &(owner, block).encode());
+
+		crate::migrations::upgrade_storage::<Test>();
+
+		assert_eq!(
+			Proofs::<Test>::get(&claim),
+			Some(ClaimInfo { owner, created_at: block, owned_since: block })
+		);
+		assert_eq!(PoeModule::on_chain_storage_version(), 1);
+	});
+}
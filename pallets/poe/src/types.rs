@@ -0,0 +1,136 @@
+use crate::{BalanceOf, Config};
+use codec::{Decode, Encode, MaxEncodedLen};
+use frame_support::{
+	pallet_prelude::*, CloneNoBound, EqNoBound, PartialEqNoBound, RuntimeDebugNoBound,
+};
+use scale_info::TypeInfo;
+#[cfg(feature = "std")]
+use serde::{Deserialize, Serialize};
+use sp_std::vec::Vec;
+
+/// Ownership record of a claim.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo, MaxEncodedLen)]
+pub struct ClaimInfo<AccountId, BlockNumber> {
+	/// Current owner of the claim.
+	pub owner: AccountId,
+	/// Block in which the claim was first recorded. Never changes after creation.
+	pub created_at: BlockNumber,
+	/// Block in which the current owner acquired the claim.
+	pub owned_since: BlockNumber,
+}
+
+/// Descriptive record attached to a claim by its owner.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct ClaimMetadata<T: Config> {
+	/// MIME type of the notarised document, e.g. `application/pdf`.
+	pub mime_type: BoundedVec<u8, T::MaxMimeTypeLength>,
+	/// Size of the document in bytes, if disclosed.
+	pub file_size: Option<u64>,
+	/// Where the document can be retrieved.
+	pub uri: BoundedVec<u8, T::MaxUriLength>,
+	/// Free-form description.
+	pub description: BoundedVec<u8, T::MaxDescriptionLength>,
+}
+
+/// Claim waiting for its co-signers. It is moved to `Proofs` once `threshold` of them attested.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct PendingClaim<T: Config> {
+	/// Account that submitted the claim and will own it once finalized.
+	pub creator: T::AccountId,
+	/// Accounts allowed to attest the claim.
+	pub cosigners: BoundedVec<T::AccountId, T::MaxCoSigners>,
+	/// Co-signers that attested so far.
+	pub attestations: BoundedVec<T::AccountId, T::MaxCoSigners>,
+	/// Number of attestations needed to finalize the claim.
+	pub threshold: u32,
+	/// Block in which the claim was submitted.
+	pub submitted_at: T::BlockNumber,
+	/// Last block in which the claim can still be attested.
+	pub expires_at: T::BlockNumber,
+	/// Reserved from the creator until the claim is finalized or discarded.
+	pub deposit: BalanceOf<T>,
+}
+
+/// Domain separator of the payloads signed for `create_claim_signed`.
+pub const CLAIM_SIGNING_CONTEXT: &[u8] = b"poe/claim";
+
+/// Claim signed off-chain by its owner and submitted by a relayer with `create_claim_signed`.
+#[derive(
+	CloneNoBound,
+	PartialEqNoBound,
+	EqNoBound,
+	RuntimeDebugNoBound,
+	Encode,
+	Decode,
+	TypeInfo,
+	MaxEncodedLen,
+)]
+#[scale_info(skip_type_params(T))]
+#[codec(mel_bound())]
+pub struct PreSignedClaim<T: Config> {
+	/// The claim to record.
+	pub claim: BoundedVec<u8, T::MaxClaimLength>,
+	/// Account that signed the payload and will own the claim.
+	pub signer: T::AccountId,
+	/// Must equal the signer's current `ClaimNonces` entry; prevents replays.
+	pub nonce: u64,
+	/// Last block in which the payload can be submitted.
+	pub deadline: T::BlockNumber,
+}
+
+/// Claim as returned by the runtime API.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct ClaimDetails<AccountId, BlockNumber> {
+	pub owner: AccountId,
+	pub created_at: BlockNumber,
+	pub owned_since: BlockNumber,
+	/// Previous owners, oldest first, with the block each of them became owner.
+	pub previous_owners: Vec<(AccountId, BlockNumber)>,
+	pub metadata: Option<MetadataDetails>,
+}
+
+/// Unbounded copy of [`ClaimMetadata`] for use outside the runtime.
+#[derive(Clone, PartialEq, Eq, RuntimeDebug, Encode, Decode, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+#[cfg_attr(feature = "std", serde(rename_all = "camelCase"))]
+pub struct MetadataDetails {
+	pub mime_type: Vec<u8>,
+	pub file_size: Option<u64>,
+	pub uri: Vec<u8>,
+	pub description: Vec<u8>,
+}
+
+impl<T: Config> From<ClaimMetadata<T>> for MetadataDetails {
+	fn from(metadata: ClaimMetadata<T>) -> Self {
+		MetadataDetails {
+			mime_type: metadata.mime_type.into_inner(),
+			file_size: metadata.file_size,
+			uri: metadata.uri.into_inner(),
+			description: metadata.description.into_inner(),
+		}
+	}
+}
@@ -52,6 +52,8 @@ pub use pallet_template;
 
 pub use pallet_kitties;
 
+pub use pallet_poe;
+
 /// An index to a block.
 pub type BlockNumber = u32;
 
@@ -107,10 +109,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 300,
+	spec_version: 301,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -277,6 +279,12 @@ impl pallet_insecure_randomness_collective_flip::Config for Runtime{}
 parameter_types! {
 	pub KittyPalletId: PalletId = PalletId(*b"py/kitty");
 	pub KittyPrice: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub KittyPriceIncrease: Permill = Permill::from_percent(5);
+	pub KittyPriceDecay: Permill = Permill::from_parts(100);
+	pub KittySalePriceWeight: Permill = Permill::from_percent(20);
+	pub KittyBreederShare: Permill = Permill::from_percent(10);
+	pub KittyCreatorRoyalty: Permill = Permill::from_percent(5);
+	pub KittyPotRoyalty: Permill = Permill::from_percent(1);
 }
 
 /// Configure the pallet-kitties in pallets/kitties.
@@ -286,6 +294,66 @@ impl pallet_kitties::Config for Runtime {
 	type Currency = Balances;
 	type KittyPrice = KittyPrice;
 	type PalletId = KittyPalletId;
+	type PriceIncrease = KittyPriceIncrease;
+	type PriceDecay = KittyPriceDecay;
+	type SalePriceWeight = KittySalePriceWeight;
+	type SpendOrigin = frame_system::EnsureRoot<AccountId>;
+	type DistributionPeriod = ConstU32<DAYS>;
+	type BreederShare = KittyBreederShare;
+	type MaxRewardedBreeders = ConstU32<100>;
+	type CreatorRoyalty = KittyCreatorRoyalty;
+	type PotRoyalty = KittyPotRoyalty;
+	type MaxOffersPerKitty = ConstU32<32>;
+	type MinOffer = ConstU128<{ EXISTENTIAL_DEPOSIT * 2 }>;
+	type MaxOfferDuration = ConstU32<{ 30 * DAYS }>;
+	type MaxChildren = ConstU32<256>;
+	type InbreedingGenerations = ConstU32<3>;
+	type MaxPedigreeDepth = ConstU32<8>;
+	type MaxLeasesPerBlock = ConstU32<64>;
+	type EntryFee = KittyPrice;
+	type CompetitionPeriod = ConstU32<HOURS>;
+	type MaxEntrants = ConstU32<64>;
+	type MaxSupply = ConstU32<1_000_000>;
+	type MintLimitPerPeriod = ConstU32<10>;
+	type MintPeriod = ConstU32<DAYS>;
+	type MintOrigin = frame_system::EnsureRoot<AccountId>;
+	type SoulboundOrigin = frame_system::EnsureRoot<AccountId>;
+	type KeyLimit = ConstU32<32>;
+	type ValueLimit = ConstU32<256>;
+	type MaxAttributes = ConstU32<16>;
+	type UriLimit = ConstU32<128>;
+	type MaxBatchSize = ConstU32<64>;
+	type MaxSwapKitties = ConstU32<16>;
+	type OnKittyEvent = ();
+}
+
+parameter_types! {
+	pub const PoeMetadataDepositBase: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub const PoeMetadataDepositPerByte: Balance = EXISTENTIAL_DEPOSIT / 10;
+	pub const PoeMerkleClaimDeposit: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub const PoePendingClaimDeposit: Balance = EXISTENTIAL_DEPOSIT * 10;
+	pub const PoeDelegationDeposit: Balance = EXISTENTIAL_DEPOSIT;
+}
+
+/// Configure the pallet-poe in pallets/poe.
+impl pallet_poe::Config for Runtime {
+	type RuntimeEvent = RuntimeEvent;
+	type MaxClaimLength = ConstU32<512>;
+	type Currency = Balances;
+	type MetadataDepositBase = PoeMetadataDepositBase;
+	type MetadataDepositPerByte = PoeMetadataDepositPerByte;
+	type MerkleClaimDeposit = PoeMerkleClaimDeposit;
+	type PendingClaimDeposit = PoePendingClaimDeposit;
+	type DelegationDeposit = PoeDelegationDeposit;
+	type MaxMimeTypeLength = ConstU32<64>;
+	type MaxUriLength = ConstU32<256>;
+	type MaxDescriptionLength = ConstU32<512>;
+	type MaxOwnershipHistory = ConstU32<16>;
+	type MaxCoSigners = ConstU32<16>;
+	type PendingClaimTimeout = ConstU32<{ 7 * DAYS }>;
+	type OffchainSignature = Signature;
+	type OffchainPublic = <Signature as Verify>::Signer;
+	type ForceOrigin = frame_system::EnsureRoot<AccountId>;
 }
 
 
@@ -308,6 +376,7 @@ construct_runtime!(
 		TemplateModule: pallet_template,
 		KittiesModule: pallet_kitties,
 		RandomnessCollectiveFlip: pallet_insecure_randomness_collective_flip,
+		PoeModule: pallet_poe,
 	}
 );
 
@@ -518,6 +587,51 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl pallet_kitties_runtime_api::KittiesApi<Block, Balance> for Runtime {
+		fn kitty_price(collection_id: pallet_kitties::CollectionId) -> Balance {
+			KittiesModule::current_price(collection_id)
+		}
+
+		fn pot_balance() -> Balance {
+			KittiesModule::pot_balance()
+		}
+
+		fn lineage(
+			kitty_id: pallet_kitties::KittyId,
+			generations: u32,
+		) -> Option<pallet_kitties::Pedigree> {
+			KittiesModule::lineage(kitty_id, generations)
+		}
+
+		fn children(kitty_id: pallet_kitties::KittyId) -> Vec<pallet_kitties::KittyId> {
+			KittiesModule::kitty_children(kitty_id).into_inner()
+		}
+
+		fn is_transferable(kitty_id: pallet_kitties::KittyId) -> bool {
+			KittiesModule::is_transferable(kitty_id)
+		}
+
+		fn token_uri(kitty_id: pallet_kitties::KittyId) -> Option<Vec<u8>> {
+			KittiesModule::token_uri(kitty_id)
+		}
+
+		fn attributes(
+			kitty_id: pallet_kitties::KittyId,
+		) -> Vec<(pallet_kitties::AttributeNamespace, Vec<u8>, Vec<u8>)> {
+			KittiesModule::kitty_attributes(kitty_id)
+		}
+	}
+
+	impl pallet_poe_runtime_api::PoeApi<Block, AccountId, BlockNumber, Hash> for Runtime {
+		fn claim(claim: Vec<u8>) -> Option<pallet_poe::ClaimDetails<AccountId, BlockNumber>> {
+			PoeModule::claim_details(claim)
+		}
+
+		fn verify_merkle_inclusion(root: Hash, leaf: Hash, index: u32, proof: Vec<Hash>) -> bool {
+			PoeModule::verify_merkle_inclusion(root, leaf, index, proof)
+		}
+	}
+
 	#[cfg(feature = "runtime-benchmarks")]
 	impl frame_benchmarking::Benchmark<Block> for Runtime {
 		fn benchmark_metadata(extra: bool) -> (